@@ -0,0 +1,147 @@
+//! Throughput of bulk charge assignment.
+//!
+//! Run with `cargo bench`. Each case charges a synthetic system built from every residue
+//! template of a scheme and reports the time per atom.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ffcharge::{
+    AtomKey, IonScheme, NucleicScheme, Position, ProteinScheme, ResidueTemplate, WaterScheme,
+};
+
+/// Number of times the residue set is repeated to form the system.
+const COPIES: usize = 2_000;
+
+/// Minimum measurement time per case.
+const MIN_TIME: Duration = Duration::from_millis(500);
+
+/// A residue instance of the synthetic system: position, residue name and atoms.
+type Residue = (Position, &'static str, ResidueTemplate);
+
+/// Builds a system from every residue template of a scheme.
+fn system(
+    positions: &[Position],
+    residues: impl Fn(Position) -> &'static [&'static str],
+    template: impl Fn(Position, &str) -> Option<ResidueTemplate>,
+) -> Vec<Residue> {
+    let mut system = Vec::new();
+    for &pos in positions {
+        for &res in residues(pos) {
+            system.push((pos, res, template(pos, res).unwrap()));
+        }
+    }
+    system.repeat(COPIES)
+}
+
+fn atom_count(system: &[Residue]) -> usize {
+    system.iter().map(|(_, _, t)| t.len()).sum()
+}
+
+/// Runs `f` until `MIN_TIME` has elapsed and prints the time per atom.
+fn bench(name: &str, atoms: usize, mut f: impl FnMut() -> f32) {
+    black_box(f());
+
+    let mut iterations = 0u32;
+    let start = Instant::now();
+    while start.elapsed() < MIN_TIME {
+        black_box(f());
+        iterations += 1;
+    }
+    let elapsed = start.elapsed();
+
+    let per_atom = elapsed.as_secs_f64() * 1e9 / (atoms as f64 * iterations as f64);
+    println!(
+        "{:<32} {:>8.2} ns/atom {:>8.1} Matoms/s",
+        name,
+        per_atom,
+        1e3 / per_atom
+    );
+}
+
+fn main() {
+    for &scheme in ProteinScheme::all() {
+        let system = system(
+            scheme.positions(),
+            |pos| scheme.residues(pos),
+            |pos, res| scheme.residue(pos, res),
+        );
+        let system = black_box(system.as_slice());
+        let atoms = atom_count(system);
+        bench(&format!("protein/{:?}/charge", scheme), atoms, || {
+            system
+                .iter()
+                .flat_map(|(pos, res, t)| {
+                    t.atom_names()
+                        .map(move |atom| scheme.charge(*pos, res, atom))
+                })
+                .map(Option::unwrap)
+                .sum()
+        });
+        bench(&format!("protein/{:?}/handle", scheme), atoms, || {
+            system
+                .iter()
+                .map(|(pos, res, _)| scheme.resolve(*pos, res).unwrap())
+                .map(|h| (0..h.len()).filter_map(|i| h.charge_by_id(i)).sum::<f32>())
+                .sum()
+        });
+        let keys: Vec<_> = system
+            .iter()
+            .flat_map(|(pos, res, t)| {
+                t.atom_names()
+                    .map(move |atom| AtomKey::new(*pos, res, atom))
+            })
+            .collect();
+        let mut out = vec![0.0; keys.len()];
+        bench(&format!("protein/{:?}/assign_into", scheme), atoms, || {
+            scheme.assign_into(&keys, &mut out);
+            out[0]
+        });
+        bench(&format!("protein/{:?}/residue", scheme), atoms, || {
+            system
+                .iter()
+                .map(|(pos, res, _)| scheme.residue(*pos, res).unwrap().net_charge())
+                .sum()
+        });
+    }
+
+    for &scheme in NucleicScheme::all() {
+        let system = system(
+            scheme.positions(),
+            |pos| scheme.residues(pos),
+            |pos, res| scheme.residue(pos, res),
+        );
+        let system = black_box(system.as_slice());
+        bench(
+            &format!("nucleic/{:?}/charge", scheme),
+            atom_count(system),
+            || {
+                system
+                    .iter()
+                    .flat_map(|(pos, res, t)| {
+                        t.atom_names()
+                            .map(move |atom| scheme.charge(*pos, res, atom))
+                    })
+                    .map(Option::unwrap)
+                    .sum()
+            },
+        );
+    }
+
+    let waters = COPIES * 100;
+    for &scheme in WaterScheme::all() {
+        bench(&format!("water/{:?}/charges", scheme), waters * 3, || {
+            (0..waters)
+                .map(|_| black_box(scheme).charges().unwrap().o)
+                .sum()
+        });
+    }
+
+    for &scheme in IonScheme::all() {
+        let ions = scheme.residues().repeat(COPIES);
+        let ions = black_box(ions.as_slice());
+        bench(&format!("ion/{:?}/charge", scheme), ions.len(), || {
+            ions.iter().map(|res| scheme.charge(res).unwrap()).sum()
+        });
+    }
+}
@@ -0,0 +1,2109 @@
+//! Code generation of the charge tables, shared by the build script and its tests.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// =============================================================================
+// Data Structures
+// =============================================================================
+
+#[derive(Debug, serde::Deserialize)]
+struct Record {
+    scheme: String,
+    position: String,
+    residue: String,
+    atom: String,
+    charge: f32,
+    /// Exact charge in units of `1 / CHARGE_SCALE` e, parsed from the `charge` text.
+    #[serde(skip)]
+    units: Option<Result<i32, UnitsError>>,
+    /// Expected net charge of the residue template, if declared on any of its rows.
+    #[serde(default)]
+    net_charge: Option<f32>,
+}
+
+impl Record {
+    /// Returns the exact charge of a residue template row.
+    ///
+    /// Panics if the charge cannot be stored exactly. Water and ion charges are only
+    /// stored as `f32` and may carry more decimal places.
+    fn exact_units(&self, location: &Location) -> i32 {
+        match self.units.expect("charge units are parsed on load") {
+            Ok(units) => units,
+            Err(e) => panic!("{}: charge {} {}", location, self.charge, e),
+        }
+    }
+}
+
+/// Reason a charge cannot be stored exactly in units of `1 / CHARGE_SCALE` e.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnitsError {
+    /// More than seven decimal places.
+    Precision,
+    /// Beyond the `i32` range of charge units (about ±214 e).
+    Overflow,
+    /// Not a plain decimal number (e.g., "1e-3").
+    Syntax,
+}
+
+impl fmt::Display for UnitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Precision => write!(f, "has more than seven decimal places"),
+            Self::Overflow => write!(f, "is out of range (at most ±214 e)"),
+            Self::Syntax => write!(f, "is not a plain decimal number"),
+        }
+    }
+}
+
+/// File and line of a CSV row, for diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    file: String,
+    line: u64,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ResidueAliasRecord {
+    convention: String,
+    alias: String,
+    residue: String,
+    position: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct AtomAliasRecord {
+    convention: String,
+    residue: String,
+    alias: String,
+    atom: String,
+}
+
+#[derive(Debug, Default)]
+struct WaterData {
+    o: Option<f32>,
+    h1: Option<f32>,
+    h2: Option<f32>,
+}
+
+/// Molecule class of a residue template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Protein,
+    Nucleic,
+}
+
+impl Class {
+    /// Returns the identifier prefix used for generated items of this class.
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Protein => "PROTEIN",
+            Self::Nucleic => "NUCLEIC",
+        }
+    }
+
+    /// Returns the schemes of this class in declaration order of its scheme enum.
+    fn schemes(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Protein => PROTEIN_SCHEMES,
+            Self::Nucleic => NUCLEIC_SCHEMES,
+        }
+    }
+}
+
+/// Protein scheme keys and their cargo features, in declaration order of
+/// `crate::ProteinScheme`.
+const PROTEIN_SCHEMES: &[(&str, &str)] = &[
+    ("amber-ffsb", "amber-ffsb"),
+    ("amber-ff03", "amber-ff03"),
+    ("charmm", "charmm"),
+];
+
+/// Nucleic acid scheme keys and their cargo features, in declaration order of
+/// `crate::NucleicScheme`.
+const NUCLEIC_SCHEMES: &[(&str, &str)] = &[("amber", "amber-nucleic"), ("charmm", "charmm")];
+
+/// Water scheme keys and their cargo features, in declaration order of `crate::WaterScheme`.
+const WATER_SCHEMES: &[(&str, &str)] = &[
+    ("tip3p", "water-tip3p"),
+    ("tip3p-fb", "water-tip3p-fb"),
+    ("spc", "water-spc"),
+    ("spc-e", "water-spc-e"),
+    ("opc3", "water-opc3"),
+];
+
+/// Ion scheme keys and their cargo features, in declaration order of `crate::IonScheme`.
+const ION_SCHEMES: &[(&str, &str)] = &[("classic", "ions")];
+
+/// All built-in schemes as (key, cargo feature) pairs.
+pub(crate) fn builtin_schemes() -> impl Iterator<Item = &'static (&'static str, &'static str)> {
+    [PROTEIN_SCHEMES, NUCLEIC_SCHEMES, WATER_SCHEMES, ION_SCHEMES]
+        .into_iter()
+        .flatten()
+}
+
+/// Bundled charge data.
+pub(crate) const CHARGES_PATH: &str = "data/charges.csv";
+/// Bundled templates derived from the terminal templates, not published parameters.
+pub(crate) const DERIVED_PATH: &str = "data/derived.csv";
+/// Bundled residue aliases.
+pub(crate) const RESIDUE_ALIASES_PATH: &str = "data/residue_aliases.csv";
+/// Bundled atom aliases.
+pub(crate) const ATOM_ALIASES_PATH: &str = "data/atom_aliases.csv";
+
+/// Position keys in declaration order of `crate::Position`.
+const POSITIONS: &[&str] = &["n", "n-", "c", "c+", "nc", "n-c+", "5", "3", "53", "m"];
+
+/// Position keys that only occur in nucleic acid chains.
+const NUCLEIC_POSITIONS: &[&str] = &["5", "3", "53"];
+
+/// Maximum deviation of a residue net charge from its expected value.
+const NET_CHARGE_TOLERANCE: f64 = 1e-5;
+
+/// Cargo feature selecting the compact residue table layout.
+pub(crate) const COMPACT_FEATURE: &str = "compact";
+
+/// Cargo feature including the derived templates of `DERIVED_PATH`.
+pub(crate) const DERIVED_FEATURE: &str = "derived-templates";
+
+/// Number of charge units per elementary charge, matching the seven decimal places of
+/// the charge files.
+const CHARGE_SCALE: f64 = 1e7;
+
+/// Heavy atoms a terminal template adds to (`+`) or removes from (`-`) the middle
+/// template of the same residue.
+const TERMINAL_HEAVY_ATOMS: &[(&str, &[&str], &[&str])] = &[
+    ("n", &[], &[]),
+    ("n-", &[], &[]),
+    ("c", &["OXT"], &[]),
+    ("c+", &["OXT"], &[]),
+    ("nc", &["OXT"], &[]),
+    ("n-c+", &["OXT"], &[]),
+    ("5", &[], &["P", "OP1", "OP2"]),
+    ("3", &[], &[]),
+    ("53", &[], &["P", "OP1", "OP2"]),
+];
+
+/// Parsed charge data organized by scheme → position → residue → atoms, with charges in
+/// units of `1 / CHARGE_SCALE` e.
+type AtomData = BTreeMap<String, BTreeMap<String, BTreeMap<String, Vec<(String, i32)>>>>;
+/// Residue template key: (scheme, position, residue).
+type TemplateKey = (String, String, String);
+/// Atom rows organized by (scheme, position, residue, atom) → (charge, location).
+type ChargeRows = BTreeMap<(String, String, String, String), (f32, Location)>;
+/// First row of each residue template.
+type TemplateSources = BTreeMap<TemplateKey, Location>;
+/// Declared net charge of residue templates, with the declaring row.
+type DeclaredCharges = BTreeMap<TemplateKey, (f32, Location)>;
+/// Label (`MAP/RESIDUE`) and atoms of a generated residue template.
+type Entry<'a> = (String, &'a [(String, i32)]);
+/// Ion data organized by scheme → residue → (atom, charge).
+type IonData = BTreeMap<String, BTreeMap<String, (String, f32)>>;
+/// Water data organized by scheme → WaterData.
+type WaterMap = BTreeMap<String, WaterData>;
+/// Residue aliases organized by convention → alias → (residue, position).
+type ResidueAliasData = BTreeMap<String, BTreeMap<String, (String, String)>>;
+/// Atom aliases organized by convention → residue → alias → atom.
+type AtomAliasData = BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>;
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Sanitizes a string for use as a Rust identifier.
+fn sanitize(s: &str) -> String {
+    s.replace('-', "_").replace('+', "_plus")
+}
+
+/// Returns `true` if the scheme key belongs to one of the crate's scheme enums.
+fn is_builtin_scheme(scheme: &str) -> bool {
+    builtin_schemes().any(|(key, _)| *key == scheme)
+}
+
+/// Returns the `crate::ExtraScheme` constant name for a scheme key.
+fn extra_const_name(scheme: &str) -> String {
+    let valid = scheme.starts_with(|c: char| c.is_ascii_lowercase())
+        && scheme
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+    if !valid {
+        panic!(
+            "Scheme key must be lowercase ASCII letters, digits and '-', starting with a letter: {}",
+            scheme
+        );
+    }
+    scheme.replace('-', "_").to_uppercase()
+}
+
+/// Generates a PHF map name from molecule class, scheme and position.
+fn map_name(class: Class, scheme: &str, pos: &str) -> String {
+    let scheme_ident = sanitize(scheme).to_uppercase();
+    let pos_ident = if pos.is_empty() {
+        "EMPTY".to_string()
+    } else {
+        sanitize(pos).to_uppercase()
+    };
+    format!("MAP_{}_{}_{}", class.prefix(), scheme_ident, pos_ident)
+}
+
+/// Returns the `crate::Position` variant for a position key.
+fn position_variant(pos: &str) -> &'static str {
+    match pos {
+        "n" => "NTerminal",
+        "n-" => "NTerminalDeprotonated",
+        "c" => "CTerminal",
+        "c+" => "CTerminalProtonated",
+        "nc" => "NCTerminal",
+        "n-c+" => "NCTerminalNeutral",
+        "5" => "FivePrime",
+        "3" => "ThreePrime",
+        "53" => "FiveThreePrime",
+        "m" => "Middle",
+        other => panic!("Unknown position: {}", other),
+    }
+}
+
+/// Formats the atoms of a residue, with charges at `offset` in the charge pool, as a
+/// `ResidueEntry` literal.
+fn residue_entry(atoms: &[(String, i32)], offset: usize) -> String {
+    let atoms_str = atoms
+        .iter()
+        .map(|(a, q)| format!("(\"{}\", {}_f32)", a, decode_charge(*q)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "ResidueEntry {{ atoms: &[{}], index: {}, charges: {} }}",
+        atoms_str,
+        atom_index(atoms.iter().map(|(atom, _)| atom.as_str())),
+        offset
+    )
+}
+
+/// Formats a perfect-hash map from atom name to position in `atoms`.
+fn atom_index<'a>(atoms: impl Iterator<Item = &'a str>) -> String {
+    let mut index = phf_codegen::Map::new();
+    for (i, atom) in atoms.enumerate() {
+        index.entry(atom, i.to_string());
+    }
+    index.build().to_string()
+}
+
+/// Parses a decimal charge into units of `1 / CHARGE_SCALE` e.
+///
+/// Fails if the charge is not a plain decimal number, has more than seven decimal places
+/// or is out of range.
+fn parse_charge_units(text: &str) -> Result<i32, UnitsError> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    if (int.is_empty() && frac.is_empty())
+        || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return Err(UnitsError::Syntax);
+    }
+    if frac.len() > 7 {
+        return Err(UnitsError::Precision);
+    }
+    let units: i32 = format!("{}{:0<7}", int, frac)
+        .parse()
+        .map_err(|_| UnitsError::Overflow)?;
+    Ok(if negative { -units } else { units })
+}
+
+/// Converts a charge in units of `1 / CHARGE_SCALE` e to the nearest `f32`, exactly as
+/// `Charge::to_f32` does.
+fn decode_charge(q: i32) -> f32 {
+    (f64::from(q) / CHARGE_SCALE) as f32
+}
+
+/// Returns the net charge of atoms with charges in units of `1 / CHARGE_SCALE` e.
+fn net_charge(atoms: &[(String, i32)]) -> f64 {
+    atoms.iter().map(|(_, q)| i64::from(*q)).sum::<i64>() as f64 / CHARGE_SCALE
+}
+
+/// Formats a list of names as a sorted static string slice literal.
+fn str_slice<'a>(names: impl Iterator<Item = &'a String>) -> String {
+    let mut names: Vec<_> = names.collect();
+    names.sort();
+    let items = names
+        .iter()
+        .map(|n| format!("\"{}\"", n))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("&[{}]", items)
+}
+
+// =============================================================================
+// Compact Layout
+// =============================================================================
+
+/// Exact charges of all residue templates in units of 1e-7 e, with identical charge
+/// lists stored once.
+struct ChargePool {
+    /// Charges of all distinct charge lists, concatenated.
+    charges: Vec<i32>,
+    /// Offset of the charges of each template in `charges`.
+    offsets: Vec<usize>,
+}
+
+impl ChargePool {
+    fn new(entries: &[Entry<'_>]) -> Self {
+        let mut pool = Self {
+            charges: Vec::new(),
+            offsets: Vec::new(),
+        };
+        let mut offsets = BTreeMap::new();
+        for (_, atoms) in entries {
+            let charges: Vec<i32> = atoms.iter().map(|(_, q)| *q).collect();
+            let offset = *offsets.entry(charges.clone()).or_insert_with(|| {
+                pool.charges.extend(&charges);
+                pool.charges.len() - charges.len()
+            });
+            pool.offsets.push(offset);
+        }
+        assert!(
+            pool.charges.len() <= u32::MAX as usize,
+            "Too many charges: {}",
+            pool.charges.len()
+        );
+        pool
+    }
+
+    fn write(&self, f: &mut dyn Write) {
+        writeln!(
+            f,
+            "static CHARGES: [i32; {}] = [{}];",
+            self.charges.len(),
+            self.charges
+                .iter()
+                .map(i32::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+    }
+}
+
+/// Residue templates with interned atom names and shared atom layouts, as emitted with
+/// the `compact` feature.
+///
+/// Residues with the same atoms in the same order (e.g., a residue in AMBER ffSB and
+/// AMBER ff03) share one layout and its atom index, and charges are only stored in the
+/// [`ChargePool`].
+struct CompactTables<'a> {
+    /// Distinct atom names, sorted.
+    names: Vec<&'a str>,
+    /// Distinct atom name orders, in order of first use.
+    layouts: Vec<Vec<&'a str>>,
+    /// Layout index of each template.
+    entries: Vec<usize>,
+}
+
+impl<'a> CompactTables<'a> {
+    fn new(entries: &[Entry<'a>]) -> Self {
+        let names: BTreeSet<&str> = entries
+            .iter()
+            .flat_map(|(_, atoms)| atoms.iter().map(|(atom, _)| atom.as_str()))
+            .collect();
+        assert!(
+            names.len() <= u16::MAX as usize,
+            "Too many distinct atom names: {}",
+            names.len()
+        );
+
+        let mut tables = Self {
+            names: names.into_iter().collect(),
+            layouts: Vec::new(),
+            entries: Vec::new(),
+        };
+        let mut layout_ids = BTreeMap::new();
+        for (_, atoms) in entries {
+            let layout: Vec<&str> = atoms.iter().map(|(atom, _)| atom.as_str()).collect();
+            let layout_id = *layout_ids.entry(layout.clone()).or_insert_with(|| {
+                tables.layouts.push(layout);
+                tables.layouts.len() - 1
+            });
+            tables.entries.push(layout_id);
+        }
+        assert!(
+            tables.layouts.len() <= u16::MAX as usize,
+            "Too many atom layouts: {}",
+            tables.layouts.len()
+        );
+        tables
+    }
+
+    fn write(&self, f: &mut dyn Write, pool: &ChargePool) {
+        writeln!(
+            f,
+            "pub static ATOM_NAMES: [&str; {}] = [{}];",
+            self.names.len(),
+            self.names
+                .iter()
+                .map(|n| format!("\"{}\"", n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+
+        let layouts: Vec<_> = self
+            .layouts
+            .iter()
+            .map(|layout| {
+                let ids: Vec<_> = layout
+                    .iter()
+                    .map(|atom| self.names.binary_search(atom).unwrap().to_string())
+                    .collect();
+                format!(
+                    "AtomLayout {{ names: &[{}], index: {} }}",
+                    ids.join(", "),
+                    atom_index(layout.iter().copied())
+                )
+            })
+            .collect();
+        writeln!(
+            f,
+            "static ATOM_LAYOUTS: [AtomLayout; {}] = [{}];",
+            layouts.len(),
+            layouts.join(", ")
+        )
+        .unwrap();
+
+        writeln!(
+            f,
+            "static RESIDUE_ENTRIES: [ResidueEntry; {}] = [{}];",
+            self.entries.len(),
+            self.entries
+                .iter()
+                .zip(&pool.offsets)
+                .map(|(layout, offset)| {
+                    format!("ResidueEntry {{ layout: {}, charges: {} }}", layout, offset)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+    }
+}
+
+// =============================================================================
+// Code Generation
+// =============================================================================
+
+/// Generates `codegen.rs` from the charge and alias files.
+///
+/// All data is kept in `BTreeMap`s and `phf_codegen` uses a fixed seed, so the generated
+/// source depends only on the input files and is byte-identical between builds.
+pub(crate) struct CodeGenerator {
+    protein_data: AtomData,
+    nucleic_data: AtomData,
+    ion_data: IonData,
+    water_data: WaterMap,
+    residue_aliases: ResidueAliasData,
+    atom_aliases: AtomAliasData,
+    extra_data: AtomData,
+    charges: ChargeRows,
+    sources: TemplateSources,
+    declared: DeclaredCharges,
+    /// Enabled cargo features; tables are only generated for the schemes they select.
+    features: HashSet<String>,
+    max_name_len: usize,
+}
+
+impl CodeGenerator {
+    fn new(features: HashSet<String>) -> Self {
+        Self {
+            protein_data: BTreeMap::new(),
+            nucleic_data: BTreeMap::new(),
+            ion_data: BTreeMap::new(),
+            water_data: BTreeMap::new(),
+            residue_aliases: BTreeMap::new(),
+            atom_aliases: BTreeMap::new(),
+            extra_data: BTreeMap::new(),
+            charges: BTreeMap::new(),
+            sources: BTreeMap::new(),
+            declared: BTreeMap::new(),
+            features,
+            max_name_len: 0,
+        }
+    }
+
+    /// Loads and validates the bundled data followed by the extra charge files.
+    ///
+    /// All data is validated, but only the schemes selected by `features` are generated.
+    pub(crate) fn from_files(extra_paths: &[PathBuf], features: HashSet<String>) -> Self {
+        let mut generator = Self::new(features);
+        generator.load(Path::new(CHARGES_PATH), false);
+        if generator.features.contains(DERIVED_FEATURE) {
+            generator.load(Path::new(DERIVED_PATH), false);
+        }
+        for path in extra_paths {
+            generator.load(path, true);
+        }
+        generator.load_residue_aliases(Path::new(RESIDUE_ALIASES_PATH));
+        generator.load_atom_aliases(Path::new(ATOM_ALIASES_PATH));
+        generator.validate();
+        generator
+    }
+
+    /// Loads a charge file.
+    ///
+    /// Only `extra` files may introduce scheme keys outside the crate's scheme enums.
+    fn load(&mut self, path: &Path, extra: bool) {
+        let source = path.display().to_string();
+        let mut rdr = csv::ReaderBuilder::new()
+            .comment(Some(b'#'))
+            .trim(csv::Trim::All)
+            .from_path(path)
+            .unwrap_or_else(|e| panic!("Failed to open {}: {}", source, e));
+
+        let headers = rdr
+            .headers()
+            .unwrap_or_else(|e| panic!("Failed to read header of {}: {}", source, e))
+            .clone();
+        let charge_column = headers
+            .iter()
+            .position(|h| h == "charge")
+            .unwrap_or_else(|| panic!("{}: missing column 'charge'", source));
+        let records: Vec<(Record, Location)> = rdr
+            .records()
+            .map(|result| {
+                let raw = result.unwrap_or_else(|e| panic!("{}: {}", source, e));
+                let location = Location {
+                    file: source.clone(),
+                    line: raw.position().map_or(0, |p| p.line()),
+                };
+                let mut record: Record = raw
+                    .deserialize(Some(&headers))
+                    .unwrap_or_else(|e| panic!("{}: {}", location, e));
+                record.units = Some(parse_charge_units(&raw[charge_column]));
+                (record, location)
+            })
+            .collect();
+
+        // Residues with 5'/3' templates are nucleotides; everything else is an amino acid.
+        let mut nucleic: HashSet<String> = records
+            .iter()
+            .map(|(r, _)| r)
+            .filter(|r| NUCLEIC_POSITIONS.contains(&r.position.as_str()))
+            .map(|r| r.residue.clone())
+            .collect();
+        nucleic.extend(
+            self.nucleic_data
+                .values()
+                .flat_map(|pos_map| pos_map.values())
+                .flat_map(|res_map| res_map.keys().cloned()),
+        );
+
+        for (record, location) in records {
+            self.process_record(record, &nucleic, &location, extra);
+        }
+    }
+
+    fn load_residue_aliases(&mut self, path: &Path) {
+        let mut rdr = csv::ReaderBuilder::new()
+            .comment(Some(b'#'))
+            .trim(csv::Trim::All)
+            .from_path(path)
+            .expect("Failed to open data/residue_aliases.csv");
+
+        for result in rdr.deserialize() {
+            let record: ResidueAliasRecord = result.expect("Failed to parse CSV record");
+            if !record.position.is_empty() {
+                position_variant(&record.position);
+            }
+            if !self.has_residue(&record.residue) {
+                panic!(
+                    "Residue alias {}/{} targets unknown residue: {}",
+                    record.convention, record.alias, record.residue
+                );
+            }
+            let previous = self
+                .residue_aliases
+                .entry(record.convention.clone())
+                .or_default()
+                .insert(record.alias.clone(), (record.residue, record.position));
+            if previous.is_some() {
+                panic!(
+                    "Duplicate residue alias: {}/{}",
+                    record.convention, record.alias
+                );
+            }
+        }
+    }
+
+    fn load_atom_aliases(&mut self, path: &Path) {
+        let mut rdr = csv::ReaderBuilder::new()
+            .comment(Some(b'#'))
+            .trim(csv::Trim::All)
+            .from_path(path)
+            .expect("Failed to open data/atom_aliases.csv");
+
+        for result in rdr.deserialize() {
+            let record: AtomAliasRecord = result.expect("Failed to parse CSV record");
+            if !self.has_atom(&record.residue, &record.atom) {
+                panic!(
+                    "Atom alias {}/{}/{} targets unknown atom: {}",
+                    record.convention, record.residue, record.alias, record.atom
+                );
+            }
+            let previous = self
+                .atom_aliases
+                .entry(record.convention.clone())
+                .or_default()
+                .entry(record.residue.clone())
+                .or_default()
+                .insert(record.alias.clone(), record.atom);
+            if previous.is_some() {
+                panic!(
+                    "Duplicate atom alias: {}/{}/{}",
+                    record.convention, record.residue, record.alias
+                );
+            }
+        }
+    }
+
+    fn process_record(
+        &mut self,
+        record: Record,
+        nucleic: &HashSet<String>,
+        location: &Location,
+        extra: bool,
+    ) {
+        for name in [&record.residue, &record.atom] {
+            if name
+                .bytes()
+                .any(|b| b.is_ascii_lowercase() || b.is_ascii_whitespace())
+            {
+                panic!(
+                    "{}: name must be uppercase without whitespace: {}",
+                    location, name
+                );
+            }
+        }
+        let builtin = is_builtin_scheme(&record.scheme);
+        if !builtin && !extra {
+            panic!("{}: unknown scheme: {}", location, record.scheme);
+        }
+        if !self.check_duplicate(&record, location) {
+            return;
+        }
+        self.declare_net_charge(&record, location);
+        self.max_name_len = self
+            .max_name_len
+            .max(record.residue.len())
+            .max(record.atom.len());
+        match (record.residue.as_str(), record.scheme.as_str()) {
+            _ if !builtin => self.add_extra(&record, location),
+            ("HOH", _) => self.add_water(&record, location),
+            (_, "classic") => self.add_ion(&record, location),
+            (res, _) if nucleic.contains(res) => self.add_atom(Class::Nucleic, &record, location),
+            _ => self.add_atom(Class::Protein, &record, location),
+        }
+    }
+
+    /// Records an atom row, returning `false` if it repeats a row of an earlier file.
+    ///
+    /// Panics on duplicate rows within a file, on rows that disagree with an earlier file
+    /// and on rows that extend a residue template defined by an earlier file.
+    fn check_duplicate(&mut self, record: &Record, location: &Location) -> bool {
+        let template = (
+            record.scheme.clone(),
+            record.position.clone(),
+            record.residue.clone(),
+        );
+        let origin = self
+            .sources
+            .entry(template)
+            .or_insert_with(|| location.clone())
+            .clone();
+        let key = (
+            record.scheme.clone(),
+            record.position.clone(),
+            record.residue.clone(),
+            record.atom.clone(),
+        );
+        match self.charges.get(&key) {
+            Some((_, previous)) if previous.file == location.file => panic!(
+                "{}: duplicate atom {}/{}/{}/{} (first defined at {})",
+                location, record.scheme, record.position, record.residue, record.atom, previous
+            ),
+            Some((charge, _)) if *charge == record.charge => false,
+            Some((charge, previous)) => panic!(
+                "{}: conflicting charge for {}/{}/{}/{}: {} here, {} at {}",
+                location,
+                record.scheme,
+                record.position,
+                record.residue,
+                record.atom,
+                record.charge,
+                charge,
+                previous
+            ),
+            None if origin.file != location.file => panic!(
+                "{}: residue {}/{}/{} extends the template defined at {}",
+                location, record.scheme, record.position, record.residue, origin
+            ),
+            None => {
+                self.charges.insert(key, (record.charge, location.clone()));
+                true
+            }
+        }
+    }
+
+    /// Records the declared net charge of a row's residue template, if any.
+    fn declare_net_charge(&mut self, record: &Record, location: &Location) {
+        let Some(net_charge) = record.net_charge else {
+            return;
+        };
+        let template = (
+            record.scheme.clone(),
+            record.position.clone(),
+            record.residue.clone(),
+        );
+        match self.declared.get(&template) {
+            Some((previous, _)) if *previous == net_charge => {}
+            Some((previous, at)) => panic!(
+                "{}: residue {}/{}/{} declares net charge {}, but {} at {}",
+                location, record.scheme, record.position, record.residue, net_charge, previous, at
+            ),
+            None => {
+                self.declared
+                    .insert(template, (net_charge, location.clone()));
+            }
+        }
+    }
+
+    fn has_residue(&self, residue: &str) -> bool {
+        let in_class = |data: &AtomData| {
+            data.values()
+                .flat_map(|pos_map| pos_map.values())
+                .any(|res_map| res_map.contains_key(residue))
+        };
+        residue == "HOH"
+            || in_class(&self.protein_data)
+            || in_class(&self.nucleic_data)
+            || self.ion_data.values().any(|m| m.contains_key(residue))
+    }
+
+    /// Returns `true` if any template of the residue (any residue for `*`) has the atom.
+    fn has_atom(&self, residue: &str, atom: &str) -> bool {
+        if residue == "HOH" {
+            return matches!(atom, "O" | "H1" | "H2");
+        }
+        [&self.protein_data, &self.nucleic_data]
+            .into_iter()
+            .flat_map(|data| data.values())
+            .flat_map(|pos_map| pos_map.values())
+            .flat_map(|res_map| res_map.iter())
+            .filter(|(res, _)| residue == "*" || *res == residue)
+            .any(|(_, atoms)| atoms.iter().any(|(a, _)| a == atom))
+    }
+
+    /// Returns the keys of the schemes enabled by cargo features, in declaration order.
+    fn enabled(&self, schemes: &[(&'static str, &'static str)]) -> Vec<&'static str> {
+        schemes
+            .iter()
+            .filter(|(_, feature)| self.features.contains(*feature))
+            .map(|(key, _)| *key)
+            .collect()
+    }
+
+    /// Returns `true` if the compact residue table layout is selected.
+    fn compact(&self) -> bool {
+        self.features.contains(COMPACT_FEATURE)
+    }
+
+    fn class_data(&self, class: Class) -> &AtomData {
+        match class {
+            Class::Protein => &self.protein_data,
+            Class::Nucleic => &self.nucleic_data,
+        }
+    }
+
+    fn add_water(&mut self, record: &Record, location: &Location) {
+        let entry = self.water_data.entry(record.scheme.clone()).or_default();
+        match record.atom.as_str() {
+            "O" => entry.o = Some(record.charge),
+            "H1" => entry.h1 = Some(record.charge),
+            "H2" => entry.h2 = Some(record.charge),
+            other => panic!(
+                "{}: unknown water atom {} in {} (expected O, H1 or H2)",
+                location, other, record.scheme
+            ),
+        }
+    }
+
+    // =========================================================================
+    // Validation
+    // =========================================================================
+
+    /// Checks the net charge of every template and the atom sets of terminal templates.
+    fn validate(&self) {
+        for data in [&self.protein_data, &self.nucleic_data, &self.extra_data] {
+            for (scheme, pos_map) in data {
+                for (pos, res_map) in pos_map {
+                    for (res, atoms) in res_map {
+                        self.validate_net_charge(scheme, pos, res, net_charge(atoms));
+                        if let Some(middle) = pos_map
+                            .get("m")
+                            .or_else(|| pos_map.get(""))
+                            .and_then(|m| m.get(res))
+                        {
+                            self.validate_terminal_atoms(scheme, pos, res, atoms, middle);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (scheme, data) in &self.water_data {
+            let template = self.template_location(scheme, "", "HOH");
+            let net: f64 = [("O", data.o), ("H1", data.h1), ("H2", data.h2)]
+                .into_iter()
+                .map(|(atom, charge)| match charge {
+                    Some(c) => f64::from(c),
+                    None => panic!("{}: water {} is missing atom {}", template, scheme, atom),
+                })
+                .sum();
+            self.validate_net_charge(scheme, "", "HOH", net);
+        }
+
+        for (scheme, res_map) in &self.ion_data {
+            for (res, (_, charge)) in res_map {
+                self.validate_net_charge(scheme, "", res, f64::from(*charge));
+            }
+        }
+    }
+
+    /// Returns the first row of a residue template.
+    fn template_location(&self, scheme: &str, pos: &str, res: &str) -> &Location {
+        &self.sources[&(scheme.to_string(), pos.to_string(), res.to_string())]
+    }
+
+    /// Checks a template net charge against its declared value, or against the nearest
+    /// integer.
+    ///
+    /// Undeclared 5'- and 3'-terminal templates carry fractional charges by design, so
+    /// each is checked together with its partner: their sum must be an integer. A 5' or
+    /// 3' template without a partner must be integral on its own.
+    fn validate_net_charge(&self, scheme: &str, pos: &str, res: &str, net: f64) {
+        let key = (scheme.to_string(), pos.to_string(), res.to_string());
+        let location = self.template_location(scheme, pos, res);
+        let partner = match pos {
+            "5" => Some("3"),
+            "3" => Some("5"),
+            _ => None,
+        }
+        .and_then(|other| Some((other, self.template_net_charge(scheme, other, res)?)));
+        let declared = self.declared.get(&key).map(|(q, _)| f64::from(*q));
+        let (net, paired) = match (declared, partner) {
+            (None, Some((other, partner_net))) => (
+                net + partner_net,
+                format!(
+                    " with its {}' template at {}",
+                    other,
+                    self.template_location(scheme, other, res)
+                ),
+            ),
+            _ => (net, String::new()),
+        };
+        let (expected, what) = match declared {
+            Some(declared) => (declared, "the declared"),
+            None => (net.round(), "an integer"),
+        };
+        // Adding zero turns a rounded -0.0 into 0.0 for the diagnostic.
+        let expected = expected + 0.0;
+        if (net - expected).abs() > NET_CHARGE_TOLERANCE {
+            panic!(
+                "{}: residue {}/{}/{} has net charge {:.7}{}, expected {} {}",
+                location, scheme, pos, res, net, paired, what, expected
+            );
+        }
+    }
+
+    /// Returns the net charge of a protein, nucleic acid or extra residue template.
+    fn template_net_charge(&self, scheme: &str, pos: &str, res: &str) -> Option<f64> {
+        [&self.protein_data, &self.nucleic_data, &self.extra_data]
+            .into_iter()
+            .find_map(|data| data.get(scheme)?.get(pos)?.get(res))
+            .map(|atoms| net_charge(atoms))
+    }
+
+    /// Checks that a terminal template has the heavy atoms of the middle template, apart
+    /// from those its terminus adds or removes.
+    fn validate_terminal_atoms(
+        &self,
+        scheme: &str,
+        pos: &str,
+        res: &str,
+        atoms: &[(String, i32)],
+        middle: &[(String, i32)],
+    ) {
+        let Some((_, added, removed)) = TERMINAL_HEAVY_ATOMS.iter().find(|(p, _, _)| *p == pos)
+        else {
+            return;
+        };
+        let heavy = |atoms: &[(String, i32)]| -> HashSet<String> {
+            atoms
+                .iter()
+                .map(|(a, _)| a.clone())
+                .filter(|a| !a.starts_with('H'))
+                .collect()
+        };
+        let mut expected = heavy(middle);
+        expected.extend(added.iter().map(|a| a.to_string()));
+        expected.retain(|a| !removed.contains(&a.as_str()));
+        let actual = heavy(atoms);
+        if actual != expected {
+            let mut missing: Vec<_> = expected.difference(&actual).collect();
+            let mut unexpected: Vec<_> = actual.difference(&expected).collect();
+            missing.sort();
+            unexpected.sort();
+            panic!(
+                "{}: residue {}/{}/{} heavy atoms differ from the middle template: missing {:?}, unexpected {:?}",
+                self.template_location(scheme, pos, res),
+                scheme,
+                pos,
+                res,
+                missing,
+                unexpected
+            );
+        }
+    }
+
+    fn add_ion(&mut self, record: &Record, location: &Location) {
+        let previous = self
+            .ion_data
+            .entry(record.scheme.clone())
+            .or_default()
+            .insert(record.residue.clone(), (record.atom.clone(), record.charge));
+        if let Some((atom, _)) = previous
+            && atom != record.atom
+        {
+            panic!(
+                "{}: ion {} has atoms {} and {} (expected one)",
+                location, record.residue, atom, record.atom
+            );
+        }
+    }
+
+    fn add_extra(&mut self, record: &Record, location: &Location) {
+        extra_const_name(&record.scheme);
+        if !record.position.is_empty() {
+            position_variant(&record.position);
+        }
+        self.extra_data
+            .entry(record.scheme.clone())
+            .or_default()
+            .entry(record.position.clone())
+            .or_default()
+            .entry(record.residue.clone())
+            .or_default()
+            .push((record.atom.clone(), record.exact_units(location)));
+    }
+
+    fn add_atom(&mut self, class: Class, record: &Record, location: &Location) {
+        position_variant(&record.position);
+        let data = match class {
+            Class::Protein => &mut self.protein_data,
+            Class::Nucleic => &mut self.nucleic_data,
+        };
+        let atoms = data
+            .entry(record.scheme.clone())
+            .or_default()
+            .entry(record.position.clone())
+            .or_default()
+            .entry(record.residue.clone())
+            .or_default();
+        atoms.push((record.atom.clone(), record.exact_units(location)));
+    }
+
+    pub(crate) fn write_lib(&self, f: &mut dyn Write) {
+        writeln!(f, "// Auto-generated by build.rs - DO NOT EDIT").unwrap();
+        writeln!(f).unwrap();
+        writeln!(
+            f,
+            "/// Length in bytes of the longest residue or atom name."
+        )
+        .unwrap();
+        writeln!(f, "pub const MAX_NAME_LEN: usize = {};", self.max_name_len).unwrap();
+        writeln!(f).unwrap();
+        self.write_entry_type(f);
+
+        self.write_atom_maps(f);
+        self.write_ion_map(f);
+        self.write_water_map(f);
+        self.write_residue_class_map(f);
+        self.write_residue_alias_maps(f);
+        self.write_atom_alias_maps(f);
+
+        writeln!(f).unwrap();
+        writeln!(
+            f,
+            "// ============================================================================="
+        )
+        .unwrap();
+        writeln!(f, "// Lookup Functions").unwrap();
+        writeln!(
+            f,
+            "// ============================================================================="
+        )
+        .unwrap();
+
+        self.write_id_lookup_fn(f, "get_protein_id", Class::Protein);
+        self.write_id_lookup_fn(f, "get_nucleic_id", Class::Nucleic);
+        self.write_extra_id_lookup_fn(f);
+        self.write_entry_lookup_fn(f);
+        self.write_ion_lookup_fn(f);
+        self.write_water_lookup_fn(f);
+        self.write_residue_class_lookup_fn(f);
+        self.write_residue_alias_lookup_fn(f);
+        self.write_atom_alias_lookup_fn(f);
+
+        writeln!(f).unwrap();
+        writeln!(
+            f,
+            "// ============================================================================="
+        )
+        .unwrap();
+        writeln!(f, "// Enumeration Functions").unwrap();
+        writeln!(
+            f,
+            "// ============================================================================="
+        )
+        .unwrap();
+
+        self.write_positions_fn(f, "protein_positions", Class::Protein);
+        self.write_positions_fn(f, "nucleic_positions", Class::Nucleic);
+        self.write_residues_fn(f, "protein_residues", Class::Protein);
+        self.write_residues_fn(f, "nucleic_residues", Class::Nucleic);
+        self.write_extra_enumeration_fns(f);
+        self.write_ion_residues_fn(f);
+    }
+
+    /// Writes the `ResidueEntry` type of the selected residue table layout.
+    ///
+    /// Both layouts provide the same methods, so the crate sources do not depend on the
+    /// layout.
+    fn write_entry_type(&self, f: &mut dyn Write) {
+        if self.compact() {
+            writeln!(
+                f,
+                "/// Ordered atom names shared by residues, with a perfect-hash index from atom name to position."
+            )
+            .unwrap();
+            writeln!(f, "pub struct AtomLayout {{").unwrap();
+            writeln!(f, "    pub names: &'static [u16],").unwrap();
+            writeln!(f, "    pub index: phf::Map<&'static str, u8>,").unwrap();
+            writeln!(f, "}}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(
+                f,
+                "/// Residue as indices into `ATOM_LAYOUTS` and `CHARGES`."
+            )
+            .unwrap();
+            writeln!(f, "pub struct ResidueEntry {{").unwrap();
+            writeln!(f, "    pub layout: u16,").unwrap();
+            writeln!(f, "    pub charges: u32,").unwrap();
+            writeln!(f, "}}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "impl ResidueEntry {{").unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(f, "    fn layout(&self) -> &'static AtomLayout {{").unwrap();
+            writeln!(f, "        &ATOM_LAYOUTS[self.layout as usize]").unwrap();
+            writeln!(f, "    }}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(
+                f,
+                "    pub fn atom_index(&self, atom: &str) -> Option<usize> {{"
+            )
+            .unwrap();
+            writeln!(
+                f,
+                "        self.layout().index.get(atom).map(|&i| i as usize)"
+            )
+            .unwrap();
+            writeln!(f, "    }}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(f, "    pub fn len(&self) -> usize {{").unwrap();
+            writeln!(f, "        self.layout().names.len()").unwrap();
+            writeln!(f, "    }}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(
+                f,
+                "    pub fn charge_at(&self, index: usize) -> Option<f32> {{"
+            )
+            .unwrap();
+            writeln!(
+                f,
+                "        self.charges().get(index).map(|&q| crate::Charge::from_units(q.into()).to_f32())"
+            )
+            .unwrap();
+            writeln!(f, "    }}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(f, "    pub fn template(&self) -> crate::ResidueTemplate {{").unwrap();
+            writeln!(
+                f,
+                "        crate::ResidueTemplate::packed(self.layout().names, self.charges())"
+            )
+            .unwrap();
+            writeln!(f, "    }}").unwrap();
+        } else {
+            writeln!(
+                f,
+                "/// Ordered atoms of a residue, with a perfect-hash index from atom name to position"
+            )
+            .unwrap();
+            writeln!(f, "/// and the offset of their exact charges in `CHARGES`.").unwrap();
+            writeln!(f, "pub struct ResidueEntry {{").unwrap();
+            writeln!(f, "    pub atoms: &'static [(&'static str, f32)],").unwrap();
+            writeln!(f, "    pub index: phf::Map<&'static str, u8>,").unwrap();
+            writeln!(f, "    pub charges: u32,").unwrap();
+            writeln!(f, "}}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "impl ResidueEntry {{").unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(
+                f,
+                "    pub fn atom_index(&self, atom: &str) -> Option<usize> {{"
+            )
+            .unwrap();
+            writeln!(f, "        self.index.get(atom).map(|&i| i as usize)").unwrap();
+            writeln!(f, "    }}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(f, "    pub fn len(&self) -> usize {{").unwrap();
+            writeln!(f, "        self.atoms.len()").unwrap();
+            writeln!(f, "    }}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(
+                f,
+                "    pub fn charge_at(&self, index: usize) -> Option<f32> {{"
+            )
+            .unwrap();
+            writeln!(f, "        self.atoms.get(index).map(|&(_, c)| c)").unwrap();
+            writeln!(f, "    }}").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "    #[inline(always)]").unwrap();
+            writeln!(f, "    pub fn template(&self) -> crate::ResidueTemplate {{").unwrap();
+            writeln!(
+                f,
+                "        crate::ResidueTemplate::new(self.atoms, self.charges())"
+            )
+            .unwrap();
+            writeln!(f, "    }}").unwrap();
+        }
+        writeln!(f).unwrap();
+        writeln!(
+            f,
+            "    /// Returns the exact charges in units of 1e-7 e, in atom order."
+        )
+        .unwrap();
+        writeln!(f, "    #[inline(always)]").unwrap();
+        writeln!(f, "    pub fn charges(&self) -> &'static [i32] {{").unwrap();
+        writeln!(f, "        &CHARGES[self.charges as usize..][..self.len()]").unwrap();
+        writeln!(f, "    }}").unwrap();
+        writeln!(f).unwrap();
+        writeln!(f, "    #[inline(always)]").unwrap();
+        writeln!(f, "    pub fn charge(&self, atom: &str) -> Option<f32> {{").unwrap();
+        writeln!(f, "        self.charge_at(self.atom_index(atom)?)").unwrap();
+        writeln!(f, "    }}").unwrap();
+        writeln!(f, "}}").unwrap();
+        writeln!(f).unwrap();
+    }
+
+    fn write_atom_maps(&self, f: &mut dyn Write) {
+        writeln!(f, "#[cfg(test)]").unwrap();
+        writeln!(
+            f,
+            "pub static POSITION_KEYS: [&str; {}] = [{}];",
+            POSITIONS.len(),
+            POSITIONS
+                .iter()
+                .map(|p| format!("\"{}\"", p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+
+        let mut entries = Vec::new();
+        self.write_residue_maps(f, &mut entries);
+
+        assert!(
+            entries.len() <= u16::MAX as usize,
+            "Too many residue templates: {}",
+            entries.len()
+        );
+        for (label, atoms) in &entries {
+            assert!(
+                atoms.len() <= u8::MAX as usize,
+                "Too many atoms in {}",
+                label
+            );
+        }
+        let pool = ChargePool::new(&entries);
+        pool.write(f);
+        if self.compact() {
+            CompactTables::new(&entries).write(f, &pool);
+        } else {
+            writeln!(
+                f,
+                "static RESIDUE_ENTRIES: [ResidueEntry; {}] = [{}];",
+                entries.len(),
+                entries
+                    .iter()
+                    .zip(&pool.offsets)
+                    .map(|((_, atoms), offset)| residue_entry(atoms, *offset))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .unwrap();
+        }
+    }
+
+    /// Writes the residue maps and tables of all classes and extra schemes, appending
+    /// their templates to `entries`.
+    fn write_residue_maps<'a>(&'a self, f: &mut dyn Write, entries: &mut Vec<Entry<'a>>) {
+        for class in [Class::Protein, Class::Nucleic] {
+            self.write_class_maps(f, class, entries);
+            self.write_tables(f, class);
+        }
+        self.write_extra_maps(f, entries);
+    }
+
+    /// Writes the residue maps of a class, appending their entries to `entries`.
+    ///
+    /// Each map resolves a residue name to its index in `entries`.
+    fn write_class_maps<'a>(
+        &'a self,
+        f: &mut dyn Write,
+        class: Class,
+        entries: &mut Vec<Entry<'a>>,
+    ) {
+        let enabled = self.enabled(class.schemes());
+        for (scheme, pos_map) in self.class_data(class) {
+            if !enabled.contains(&scheme.as_str()) {
+                continue;
+            }
+            for (pos, res_map) in pos_map {
+                let name = map_name(class, scheme, pos);
+                let mut phf = phf_codegen::Map::new();
+                for (res, atoms) in res_map {
+                    phf.entry(res.as_str(), format!("{}_u16", entries.len()));
+                    entries.push((format!("{}/{}", name, res), atoms));
+                }
+                writeln!(
+                    f,
+                    "static {}: phf::Map<&'static str, u16> = {};",
+                    name,
+                    phf.build()
+                )
+                .unwrap();
+                writeln!(
+                    f,
+                    "static {}_RESIDUES: &[&str] = {};",
+                    name,
+                    str_slice(res_map.keys())
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    /// Writes the residue maps and tables of the extra schemes, appending their entries
+    /// to `entries`.
+    ///
+    /// Templates without a position are added to every position map that has no
+    /// template of its own for the residue, sharing a single entry.
+    fn write_extra_maps<'a>(&'a self, f: &mut dyn Write, entries: &mut Vec<Entry<'a>>) {
+        let schemes: Vec<_> = self.extra_data.keys().collect();
+        assert!(
+            schemes.len() <= u8::MAX as usize,
+            "Too many extra schemes: {}",
+            schemes.len()
+        );
+
+        let mut tables = Vec::new();
+        let mut residue_names = Vec::new();
+        let mut positions = Vec::new();
+        for scheme in &schemes {
+            let pos_map = &self.extra_data[*scheme];
+            let ident = extra_const_name(scheme);
+
+            let mut any_ids = BTreeMap::new();
+            for (res, atoms) in pos_map.get("").into_iter().flatten() {
+                any_ids.insert(res.as_str(), entries.len());
+                entries.push((format!("{}/{}", ident, res), atoms));
+            }
+
+            let mut table_row = Vec::new();
+            let mut names_row = Vec::new();
+            let mut positions_row = Vec::new();
+            for pos in POSITIONS {
+                let own = pos_map.get(*pos);
+                let mut residues: Vec<&String> = own.into_iter().flat_map(|m| m.keys()).collect();
+                residues.extend(
+                    pos_map
+                        .get("")
+                        .into_iter()
+                        .flat_map(|m| m.keys())
+                        .filter(|res| !own.is_some_and(|m| m.contains_key(*res))),
+                );
+                if residues.is_empty() {
+                    table_row.push("None".to_string());
+                    names_row.push("&[]".to_string());
+                    continue;
+                }
+                residues.sort();
+
+                let name = format!("MAP_EXTRA_{}_{}", ident, sanitize(pos).to_uppercase());
+                let mut phf = phf_codegen::Map::new();
+                for res in &residues {
+                    let id = match own.and_then(|m| m.get(*res)) {
+                        Some(atoms) => {
+                            entries.push((format!("{}/{}", name, res), atoms));
+                            entries.len() - 1
+                        }
+                        None => any_ids[res.as_str()],
+                    };
+                    phf.entry(res.as_str(), format!("{}_u16", id));
+                }
+                writeln!(
+                    f,
+                    "static {}: phf::Map<&'static str, u16> = {};",
+                    name,
+                    phf.build()
+                )
+                .unwrap();
+                writeln!(
+                    f,
+                    "static {}_RESIDUES: &[&str] = {};",
+                    name,
+                    str_slice(residues.into_iter())
+                )
+                .unwrap();
+                table_row.push(format!("Some(&{})", name));
+                names_row.push(format!("{}_RESIDUES", name));
+                positions_row.push(format!("crate::Position::{}", position_variant(pos)));
+            }
+            tables.push(format!("[{}]", table_row.join(", ")));
+            residue_names.push(format!("[{}]", names_row.join(", ")));
+            positions.push(format!("&[{}]", positions_row.join(", ")));
+        }
+
+        writeln!(
+            f,
+            "pub static EXTRA_SCHEME_KEYS: [&str; {}] = [{}];",
+            schemes.len(),
+            schemes
+                .iter()
+                .map(|s| format!("\"{}\"", s))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "pub static EXTRA_SCHEMES: [crate::ExtraScheme; {}] = [{}];",
+            schemes.len(),
+            (0..schemes.len())
+                .map(|i| format!("crate::ExtraScheme::from_index({})", i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+        writeln!(f, "impl crate::ExtraScheme {{").unwrap();
+        for (i, scheme) in schemes.iter().enumerate() {
+            writeln!(f, "    /// Extra scheme `{}`.", scheme).unwrap();
+            writeln!(
+                f,
+                "    pub const {}: Self = Self::from_index({});",
+                extra_const_name(scheme),
+                i
+            )
+            .unwrap();
+        }
+        writeln!(f, "}}").unwrap();
+        writeln!(
+            f,
+            "static EXTRA_TABLES: [[Option<&phf::Map<&'static str, u16>>; {}]; {}] = [{}];",
+            POSITIONS.len(),
+            schemes.len(),
+            tables.join(", ")
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "static EXTRA_RESIDUE_NAMES: [[&[&str]; {}]; {}] = [{}];",
+            POSITIONS.len(),
+            schemes.len(),
+            residue_names.join(", ")
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "static EXTRA_POSITIONS: [&[crate::Position]; {}] = [{}];",
+            schemes.len(),
+            positions.join(", ")
+        )
+        .unwrap();
+    }
+
+    /// Returns the ion data of the schemes enabled by cargo features, in declaration order
+    /// of `crate::IonScheme`.
+    fn enabled_ions(
+        &self,
+    ) -> impl Iterator<Item = (&'static str, &BTreeMap<String, (String, f32)>)> {
+        self.enabled(ION_SCHEMES).into_iter().map(|scheme| {
+            let data = self
+                .ion_data
+                .get(scheme)
+                .unwrap_or_else(|| panic!("Missing ion scheme {}", scheme));
+            (scheme, data)
+        })
+    }
+
+    fn write_ion_map(&self, f: &mut dyn Write) {
+        for (scheme, res_map) in self.enabled_ions() {
+            let name = format!("ION_MAP_{}", sanitize(scheme).to_uppercase());
+
+            let entries: Vec<_> = res_map
+                .iter()
+                .map(|(res, (atom, charge))| {
+                    (res.clone(), format!("(\"{}\", {}_f32)", atom, charge))
+                })
+                .collect();
+
+            let mut phf = phf_codegen::Map::new();
+            for (res, val) in &entries {
+                phf.entry(res.as_str(), val.as_str());
+            }
+
+            writeln!(
+                f,
+                "static {}: phf::Map<&'static str, (&'static str, f32)> = {};",
+                name,
+                phf.build()
+            )
+            .unwrap();
+        }
+
+        let maps: Vec<_> = self
+            .enabled_ions()
+            .map(|(scheme, _)| format!("&ION_MAP_{}", sanitize(scheme).to_uppercase()))
+            .collect();
+        writeln!(
+            f,
+            "static ION_MAPS: [&phf::Map<&'static str, (&'static str, f32)>; {}] = [{}];",
+            maps.len(),
+            maps.join(", ")
+        )
+        .unwrap();
+    }
+
+    fn write_water_map(&self, f: &mut dyn Write) {
+        let entries: Vec<_> = self
+            .enabled(WATER_SCHEMES)
+            .into_iter()
+            .map(|scheme| {
+                let data = self
+                    .water_data
+                    .get(scheme)
+                    .unwrap_or_else(|| panic!("Missing water scheme {}", scheme));
+                format!(
+                    "crate::WaterCharges {{ o: {}_f32, h1: {}_f32, h2: {}_f32 }}",
+                    data.o.expect("validated water O"),
+                    data.h1.expect("validated water H1"),
+                    data.h2.expect("validated water H2")
+                )
+            })
+            .collect();
+
+        writeln!(
+            f,
+            "static WATER_CHARGES: [crate::WaterCharges; {}] = [{}];",
+            entries.len(),
+            entries.join(", ")
+        )
+        .unwrap();
+    }
+
+    /// Writes the residue class map of the enabled schemes.
+    ///
+    /// Conflicting classes are checked across all data, so they fail every build.
+    fn write_residue_class_map(&self, f: &mut dyn Write) {
+        let mut all: BTreeMap<String, &str> = BTreeMap::new();
+        let mut classes: BTreeMap<String, &str> = BTreeMap::new();
+        let mut insert = |residue: &str, class: &'static str, enabled: bool| {
+            if let Some(previous) = all.insert(residue.to_string(), class)
+                && previous != class
+            {
+                panic!("Residue {} is both {} and {}", residue, previous, class);
+            }
+            if enabled {
+                classes.insert(residue.to_string(), class);
+            }
+        };
+
+        let protein = self.enabled(PROTEIN_SCHEMES);
+        for (scheme, pos_map) in &self.protein_data {
+            for residue in pos_map.values().flat_map(|m| m.keys()) {
+                insert(residue, "Protein", protein.contains(&scheme.as_str()));
+            }
+        }
+        let nucleic = self.enabled(NUCLEIC_SCHEMES);
+        for (scheme, pos_map) in &self.nucleic_data {
+            for (residue, atoms) in pos_map.values().flat_map(|m| m.iter()) {
+                let rna = atoms.iter().any(|(a, _)| a == "O2'");
+                let class = if rna { "Rna" } else { "Dna" };
+                insert(residue, class, nucleic.contains(&scheme.as_str()));
+            }
+        }
+        let water = self.enabled(WATER_SCHEMES);
+        for scheme in self.water_data.keys() {
+            insert("HOH", "Water", water.contains(&scheme.as_str()));
+        }
+        let ion = self.enabled(ION_SCHEMES);
+        for (scheme, res_map) in &self.ion_data {
+            for residue in res_map.keys() {
+                insert(residue, "Ion", ion.contains(&scheme.as_str()));
+            }
+        }
+
+        let mut phf = phf_codegen::Map::new();
+        for (residue, class) in &classes {
+            phf.entry(residue.as_str(), format!("crate::ResidueClass::{}", class));
+        }
+
+        writeln!(
+            f,
+            "static RESIDUE_CLASSES: phf::Map<&'static str, crate::ResidueClass> = {};",
+            phf.build()
+        )
+        .unwrap();
+    }
+
+    fn write_residue_alias_maps(&self, f: &mut dyn Write) {
+        for (convention, aliases) in &self.residue_aliases {
+            let name = format!("RESIDUE_ALIASES_{}", sanitize(convention).to_uppercase());
+
+            let entries: Vec<_> = aliases
+                .iter()
+                .map(|(alias, (res, pos))| {
+                    let pos = if pos.is_empty() {
+                        "None".to_string()
+                    } else {
+                        format!("Some(crate::Position::{})", position_variant(pos))
+                    };
+                    (alias.clone(), format!("(\"{}\", {})", res, pos))
+                })
+                .collect();
+
+            let mut phf = phf_codegen::Map::new();
+            for (alias, val) in &entries {
+                phf.entry(alias.as_str(), val.as_str());
+            }
+
+            writeln!(
+                f,
+                "static {}: phf::Map<&'static str, (&'static str, Option<crate::Position>)> = {};",
+                name,
+                phf.build()
+            )
+            .unwrap();
+        }
+    }
+
+    fn write_atom_alias_maps(&self, f: &mut dyn Write) {
+        for (convention, res_map) in &self.atom_aliases {
+            let name = format!("ATOM_ALIASES_{}", sanitize(convention).to_uppercase());
+
+            let entries: Vec<_> = res_map
+                .iter()
+                .map(|(res, aliases)| {
+                    let mut phf = phf_codegen::Map::new();
+                    for (alias, atom) in aliases {
+                        phf.entry(alias.as_str(), format!("\"{}\"", atom));
+                    }
+                    (res.clone(), phf.build().to_string())
+                })
+                .collect();
+
+            let mut phf = phf_codegen::Map::new();
+            for (res, val) in &entries {
+                phf.entry(res.as_str(), val.as_str());
+            }
+
+            writeln!(
+                f,
+                "static {}: phf::Map<&'static str, phf::Map<&'static str, &'static str>> = {};",
+                name,
+                phf.build()
+            )
+            .unwrap();
+        }
+    }
+
+    fn write_tables(&self, f: &mut dyn Write, class: Class) {
+        let prefix = class.prefix();
+        let data = self.class_data(class);
+        let schemes = self.enabled(class.schemes());
+        for scheme in data.keys() {
+            if !class.schemes().iter().any(|(key, _)| key == scheme) {
+                panic!("Unknown {} scheme: {}", prefix.to_lowercase(), scheme);
+            }
+        }
+
+        writeln!(f, "#[cfg(test)]").unwrap();
+        writeln!(
+            f,
+            "pub static {}_SCHEME_KEYS: [&str; {}] = [{}];",
+            prefix,
+            schemes.len(),
+            schemes
+                .iter()
+                .map(|s| format!("\"{}\"", s))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+
+        let table = self.class_table(class, |name| match name {
+            Some(name) => format!("Some(&{})", name),
+            None => "None".to_string(),
+        });
+        writeln!(
+            f,
+            "static {}_TABLES: [[Option<&phf::Map<&'static str, u16>>; {}]; {}] = {};",
+            prefix,
+            POSITIONS.len(),
+            schemes.len(),
+            table
+        )
+        .unwrap();
+
+        let table = self.class_table(class, |name| match name {
+            Some(name) => format!("{}_RESIDUES", name),
+            None => "&[]".to_string(),
+        });
+        writeln!(
+            f,
+            "static {}_RESIDUE_NAMES: [[&[&str]; {}]; {}] = {};",
+            prefix,
+            POSITIONS.len(),
+            schemes.len(),
+            table
+        )
+        .unwrap();
+
+        let rows: Vec<_> = schemes
+            .iter()
+            .map(|scheme| {
+                let variants: Vec<_> = POSITIONS
+                    .iter()
+                    .filter(|p| data.get(*scheme).is_some_and(|m| m.contains_key(**p)))
+                    .map(|p| format!("crate::Position::{}", position_variant(p)))
+                    .collect();
+                format!("&[{}]", variants.join(", "))
+            })
+            .collect();
+        writeln!(
+            f,
+            "static {}_POSITIONS: [&[crate::Position]; {}] = [{}];",
+            prefix,
+            schemes.len(),
+            rows.join(", ")
+        )
+        .unwrap();
+    }
+
+    /// Formats a `[scheme][position]` array literal over the maps of a class.
+    ///
+    /// `cell` receives the map name, or `None` if the scheme has no data at the position.
+    fn class_table<F>(&self, class: Class, cell: F) -> String
+    where
+        F: Fn(Option<&str>) -> String,
+    {
+        let data = self.class_data(class);
+        let rows: Vec<_> = self
+            .enabled(class.schemes())
+            .iter()
+            .map(|scheme| {
+                let cells: Vec<_> = POSITIONS
+                    .iter()
+                    .map(|pos| {
+                        let present = data.get(*scheme).is_some_and(|m| m.contains_key(*pos));
+                        let name = map_name(class, scheme, pos);
+                        cell(present.then_some(name.as_str()))
+                    })
+                    .collect();
+                format!("[{}]", cells.join(", "))
+            })
+            .collect();
+        format!("[{}]", rows.join(", "))
+    }
+
+    fn write_id_lookup_fn(&self, f: &mut dyn Write, fn_name: &str, class: Class) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn {}(scheme: usize, pos: usize, res: &str) -> Option<u16> {{",
+            fn_name
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "    {}_TABLES[scheme][pos]?.get(res).copied()",
+            class.prefix()
+        )
+        .unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_extra_id_lookup_fn(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn get_extra_id(scheme: usize, pos: usize, res: &str) -> Option<u16> {{"
+        )
+        .unwrap();
+        writeln!(f, "    EXTRA_TABLES[scheme][pos]?.get(res).copied()").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_entry_lookup_fn(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn get_residue_entry(id: u16) -> &'static ResidueEntry {{"
+        )
+        .unwrap();
+        writeln!(f, "    &RESIDUE_ENTRIES[id as usize]").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_ion_lookup_fn(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn get_ion(scheme: usize, res: &str) -> Option<(&'static str, f32)> {{"
+        )
+        .unwrap();
+        writeln!(f, "    ION_MAPS[scheme].get(res).copied()").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_residue_class_lookup_fn(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn get_residue_class(res: &str) -> Option<crate::ResidueClass> {{"
+        )
+        .unwrap();
+        writeln!(f, "    RESIDUE_CLASSES.get(res).copied()").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_residue_alias_lookup_fn(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn get_residue_alias(convention: &str, name: &str) -> Option<(&'static str, Option<crate::Position>)> {{"
+        )
+        .unwrap();
+        writeln!(f, "    match convention {{").unwrap();
+        for convention in self.residue_aliases.keys() {
+            let name = format!("RESIDUE_ALIASES_{}", sanitize(convention).to_uppercase());
+            writeln!(
+                f,
+                "        \"{}\" => {}.get(name).copied(),",
+                convention, name
+            )
+            .unwrap();
+        }
+        writeln!(f, "        _ => None,").unwrap();
+        writeln!(f, "    }}").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_atom_alias_lookup_fn(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn get_atom_alias(convention: &str, res: &str, name: &str) -> Option<&'static str> {{"
+        )
+        .unwrap();
+        writeln!(f, "    let map = match convention {{").unwrap();
+        for convention in self.atom_aliases.keys() {
+            let name = format!("ATOM_ALIASES_{}", sanitize(convention).to_uppercase());
+            writeln!(f, "        \"{}\" => &{},", convention, name).unwrap();
+        }
+        writeln!(f, "        _ => return None,").unwrap();
+        writeln!(f, "    }};").unwrap();
+        writeln!(f).unwrap();
+        writeln!(f, "    map.get(res)").unwrap();
+        writeln!(f, "        .and_then(|aliases| aliases.get(name))").unwrap();
+        writeln!(f, "        .or_else(|| map.get(\"*\")?.get(name))").unwrap();
+        writeln!(f, "        .copied()").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_positions_fn(&self, f: &mut dyn Write, fn_name: &str, class: Class) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn {}(scheme: usize) -> &'static [crate::Position] {{",
+            fn_name
+        )
+        .unwrap();
+        writeln!(f, "    {}_POSITIONS[scheme]", class.prefix()).unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_residues_fn(&self, f: &mut dyn Write, fn_name: &str, class: Class) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn {}(scheme: usize, pos: usize) -> &'static [&'static str] {{",
+            fn_name
+        )
+        .unwrap();
+        writeln!(f, "    {}_RESIDUE_NAMES[scheme][pos]", class.prefix()).unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_extra_enumeration_fns(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn extra_positions(scheme: usize) -> &'static [crate::Position] {{"
+        )
+        .unwrap();
+        writeln!(f, "    EXTRA_POSITIONS[scheme]").unwrap();
+        writeln!(f, "}}").unwrap();
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn extra_residues(scheme: usize, pos: usize) -> &'static [&'static str] {{"
+        )
+        .unwrap();
+        writeln!(f, "    EXTRA_RESIDUE_NAMES[scheme][pos]").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_ion_residues_fn(&self, f: &mut dyn Write) {
+        let residues: Vec<_> = self
+            .enabled_ions()
+            .map(|(_, res_map)| str_slice(res_map.keys()))
+            .collect();
+        writeln!(
+            f,
+            "static ION_RESIDUES: [&[&str]; {}] = [{}];",
+            residues.len(),
+            residues.join(", ")
+        )
+        .unwrap();
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn ion_residues(scheme: usize) -> &'static [&'static str] {{"
+        )
+        .unwrap();
+        writeln!(f, "    ION_RESIDUES[scheme]").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+
+    fn write_water_lookup_fn(&self, f: &mut dyn Write) {
+        writeln!(f).unwrap();
+        writeln!(f, "#[inline(always)]").unwrap();
+        writeln!(
+            f,
+            "pub fn get_water_charges(scheme: usize) -> Option<crate::WaterCharges> {{"
+        )
+        .unwrap();
+        writeln!(f, "    WATER_CHARGES.get(scheme).copied()").unwrap();
+        writeln!(f, "}}").unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_features() -> HashSet<String> {
+        builtin_schemes()
+            .map(|(_, feature)| *feature)
+            .chain([DERIVED_FEATURE])
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn generate(extra_paths: &[PathBuf]) -> Vec<u8> {
+        let mut out = Vec::new();
+        CodeGenerator::from_files(extra_paths, all_features()).write_lib(&mut out);
+        out
+    }
+
+    /// Loads the bundled data and one extra charge file with the given rows.
+    fn load_extra(name: &str, rows: &str) {
+        let path =
+            std::env::temp_dir().join(format!("ffcharge-{}-{}.csv", std::process::id(), name));
+        std::fs::write(
+            &path,
+            format!("scheme,position,residue,atom,charge\n{}", rows),
+        )
+        .unwrap();
+        CodeGenerator::from_files(&[path], all_features());
+    }
+
+    #[test]
+    #[should_panic(expected = "-overflow.csv:2: charge 300 is out of range")]
+    fn charge_overflow() {
+        load_extra(
+            "overflow",
+            "test-ligands,,LIG,C1,300\ntest-ligands,,LIG,O1,-300\n",
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "-unpaired.csv:2: residue amber/5/DX has net charge -0.3000000, expected an integer 0"
+    )]
+    fn unpaired_five_prime_template() {
+        load_extra("unpaired", "amber,5,DX,C1,-0.1\namber,5,DX,O1,-0.2\n");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "residue amber/3/DX has net charge -0.5000000 with its 5' template at"
+    )]
+    fn paired_three_prime_template() {
+        load_extra("paired", "amber,5,DX,C1,-0.3\namber,3,DX,C1,-0.2\n");
+    }
+
+    #[test]
+    fn deterministic_output() {
+        let first = generate(&[]);
+        let second = generate(&[]);
+        assert!(!first.is_empty());
+        assert!(first == second, "Generated code differs between runs");
+    }
+
+    #[test]
+    fn deterministic_output_with_extra_data() {
+        let extra = [PathBuf::from("tests/data/extra.csv")];
+        let first = generate(&extra);
+        assert!(first != generate(&[]));
+        assert!(
+            first == generate(&extra),
+            "Generated code differs between runs"
+        );
+    }
+
+    /// Returns the size in bytes of a perfect-hash atom index over `atoms` atoms.
+    fn index_size(atoms: usize) -> usize {
+        // phf_generator uses one displacement pair per five keys.
+        size_of::<phf::Map<&str, u8>>()
+            + atoms.div_ceil(5) * size_of::<(u32, u32)>()
+            + atoms * size_of::<(&str, u8)>()
+    }
+
+    #[test]
+    fn compact_layout_size() {
+        let generator = CodeGenerator::from_files(&[], all_features());
+        let mut entries = Vec::new();
+        generator.write_residue_maps(&mut std::io::sink(), &mut entries);
+
+        // String data and the exact charge pool are shared by both layouts and not
+        // counted.
+        let default: usize = entries
+            .iter()
+            .map(|(_, atoms)| {
+                size_of::<(&[(&str, f32)], u32)>()
+                    + atoms.len() * size_of::<(&str, f32)>()
+                    + index_size(atoms.len())
+            })
+            .sum();
+        let tables = CompactTables::new(&entries);
+        let compact = tables.names.len() * size_of::<&str>()
+            + tables
+                .layouts
+                .iter()
+                .map(|l| size_of::<&[u16]>() + l.len() * size_of::<u16>() + index_size(l.len()))
+                .sum::<usize>()
+            + tables.entries.len() * size_of::<(u16, u32)>();
+
+        // The README advertises the compact tables at about a third of the default size.
+        assert!(tables.layouts.len() < entries.len() / 2);
+        assert!(
+            compact * 3 < default,
+            "compact layout is {} of {} bytes",
+            compact,
+            default
+        );
+    }
+
+    #[test]
+    fn charge_units() {
+        assert_eq!(parse_charge_units("-0.4157000"), Ok(-4_157_000));
+        assert_eq!(parse_charge_units("-1.1074"), Ok(-11_074_000));
+        assert_eq!(parse_charge_units("+2"), Ok(20_000_000));
+        assert_eq!(parse_charge_units(".5"), Ok(5_000_000));
+        assert_eq!(parse_charge_units("0.0000001"), Ok(1));
+        assert_eq!(parse_charge_units("214"), Ok(2_140_000_000));
+        assert_eq!(parse_charge_units("0.12345678"), Err(UnitsError::Precision));
+        assert_eq!(parse_charge_units("1e-3"), Err(UnitsError::Syntax));
+        assert_eq!(parse_charge_units("-"), Err(UnitsError::Syntax));
+        assert_eq!(parse_charge_units("300"), Err(UnitsError::Overflow));
+        assert_eq!(parse_charge_units("-300.5"), Err(UnitsError::Overflow));
+        // f32 cannot tell 2.0000000 from 2.0000001, so residue charges keep their units.
+        assert_eq!(decode_charge(20_000_000), decode_charge(20_000_001));
+    }
+
+    #[test]
+    fn features_select_tables() {
+        let features = HashSet::from(["amber-ffsb".to_string(), "water-tip3p".to_string()]);
+        let generator = CodeGenerator::from_files(&[], features);
+        assert_eq!(generator.enabled(PROTEIN_SCHEMES), ["amber-ffsb"]);
+        assert!(generator.enabled(NUCLEIC_SCHEMES).is_empty());
+        assert_eq!(generator.enabled(WATER_SCHEMES), ["tip3p"]);
+
+        let mut out = Vec::new();
+        generator.write_lib(&mut out);
+        let some = String::from_utf8(out).unwrap();
+        let all = String::from_utf8(generate(&[])).unwrap();
+        assert!(some.len() < all.len() / 2);
+        assert!(some.contains("MAP_PROTEIN_AMBER_FFSB_M"));
+        assert!(!some.contains("MAP_PROTEIN_CHARMM_M"));
+        assert!(!some.contains("MAP_NUCLEIC_"));
+        assert!(!some.contains("ION_MAP_CLASSIC"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Batch charge assignment.
+
+use crate::{Position, ResidueHandle};
+
+/// Position, residue and atom name of one atom in a topology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AtomKey<'a> {
+    /// Position of the residue in the chain.
+    pub position: Position,
+    /// Residue name (e.g., "ALA", "DA").
+    pub residue: &'a str,
+    /// Atom name (e.g., "CA", "P").
+    pub atom: &'a str,
+}
+
+impl<'a> AtomKey<'a> {
+    /// Creates an atom key.
+    pub const fn new(position: Position, residue: &'a str, atom: &'a str) -> Self {
+        Self {
+            position,
+            residue,
+            atom,
+        }
+    }
+}
+
+/// Outcome of a batch charge assignment.
+///
+/// Atoms that could not be charged are marked with `f32::NAN` in the output slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AssignReport {
+    /// Number of atoms that received a charge.
+    pub assigned: usize,
+    /// Number of atoms whose position, residue or atom is not defined by the scheme.
+    pub failed: usize,
+    /// Index of the first atom that could not be charged.
+    pub first_failure: Option<usize>,
+}
+
+impl AssignReport {
+    /// Returns `true` if every atom received a charge.
+    pub const fn is_complete(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Charges `atoms` into `out`, resolving each run of atoms that share a residue once.
+///
+/// # Panics
+///
+/// Panics if `atoms` and `out` have different lengths.
+pub(crate) fn assign_with<F>(atoms: &[AtomKey<'_>], out: &mut [f32], resolve: F) -> AssignReport
+where
+    F: Fn(Position, &str) -> Option<ResidueHandle>,
+{
+    assert_eq!(
+        atoms.len(),
+        out.len(),
+        "atoms and out must have the same length"
+    );
+
+    let mut report = AssignReport::default();
+    let mut cached: Option<(Position, &str, Option<ResidueHandle>)> = None;
+
+    for (i, (key, charge)) in atoms.iter().zip(out.iter_mut()).enumerate() {
+        let handle = match cached {
+            Some((pos, res, handle)) if pos == key.position && res == key.residue => handle,
+            _ => {
+                let handle = resolve(key.position, key.residue);
+                cached = Some((key.position, key.residue, handle));
+                handle
+            }
+        };
+
+        match handle.and_then(|h| h.charge(key.atom)) {
+            Some(c) => {
+                *charge = c;
+                report.assigned += 1;
+            }
+            None => {
+                *charge = f32::NAN;
+                report.failed += 1;
+                report.first_failure.get_or_insert(i);
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: &[AtomKey] = &[
+        AtomKey::new(Position::Middle, "ALA", "N"),
+        AtomKey::new(Position::Middle, "ALA", "CA"),
+        AtomKey::new(Position::Middle, "ALA", "XX"),
+        AtomKey::new(Position::Middle, "XYZ", "CA"),
+        AtomKey::new(Position::CTerminal, "GLY", "OXT"),
+    ];
+
+    #[test]
+    #[cfg(feature = "amber-ffsb")]
+    fn report() {
+        let scheme = crate::ProteinScheme::AmberFFSB;
+        let mut out = [0.0; 5];
+        let report = assign_with(KEYS, &mut out, |pos, res| scheme.resolve(pos, res));
+        assert_eq!(
+            report,
+            AssignReport {
+                assigned: 3,
+                failed: 2,
+                first_failure: Some(2),
+            }
+        );
+        assert!(!report.is_complete());
+        assert!(out[2].is_nan() && out[3].is_nan());
+        assert_eq!(
+            Some(out[4]),
+            scheme.charge(Position::CTerminal, "GLY", "OXT")
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let report = assign_with(&[], &mut [], |_, _| None);
+        assert_eq!(report, AssignReport::default());
+        assert!(report.is_complete());
+    }
+
+    #[test]
+    #[should_panic]
+    fn length_mismatch() {
+        assign_with(KEYS, &mut [0.0; 2], |_, _| None);
+    }
+}
@@ -0,0 +1,337 @@
+//! Exact fixed-point partial charges.
+
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// Partial charge as an exact multiple of 1e-7 elementary charges.
+///
+/// The charge tables carry at most seven decimal places, so every tabulated charge is
+/// represented exactly, and sums of charges are exact: the net charge of a residue or a
+/// whole chain carries no drift from adding up `f32` values. A few published templates are
+/// themselves off an integral net charge by up to 1e-5 e (e.g., the ff03 termini);
+/// [`NetCharge`] splits such sums into the formal integer charge and the residual.
+///
+/// ```rust
+/// # #[cfg(feature = "amber-ffsb")]
+/// # {
+/// use ffcharge::{Charge, NetCharge, Position, ProteinScheme};
+///
+/// let scheme = ProteinScheme::AmberFFSB;
+/// let chain = [
+///     (Position::NTerminal, "MET"),
+///     (Position::Middle, "LYS"),
+///     (Position::Middle, "ASP"),
+///     (Position::CTerminal, "GLU"),
+/// ];
+/// let templates = chain.map(|(pos, res)| scheme.residue(pos, res).unwrap());
+/// let total: Charge = templates.iter().map(|t| t.exact_net_charge()).sum();
+/// assert_eq!(total.to_integer(), Some(-1));
+///
+/// let net = NetCharge::of(templates);
+/// assert_eq!(net.formal, -1);
+/// assert!(net.is_integral());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Charge(i64);
+
+impl Charge {
+    /// Number of units per elementary charge.
+    pub const SCALE: i64 = 10_000_000;
+
+    /// Zero charge.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a charge from a number of 1e-7 e units.
+    pub const fn from_units(units: i64) -> Self {
+        Self(units)
+    }
+
+    /// Creates a charge of a whole number of elementary charges.
+    pub const fn from_integer(charge: i32) -> Self {
+        Self(charge as i64 * Self::SCALE)
+    }
+
+    /// Creates a charge from an `f32`, rounded to the nearest 1e-7 e.
+    ///
+    /// Water and ion charges are only stored as `f32`. Those with at most seven decimal
+    /// places convert exactly; others, such as the TIP3P-FB charges, round to within
+    /// 1e-7 e of the published value, so the charges of a TIP3P-FB water sum to within
+    /// 3e-7 e of zero. Residue charges of magnitude 1 or more may not convert exactly, as
+    /// `f32` cannot tell some neighbouring multiples of 1e-7 apart; use
+    /// [`ResidueTemplate::exact_charge`](crate::ResidueTemplate::exact_charge) or
+    /// [`ProteinScheme::exact_charge`](crate::ProteinScheme::exact_charge) for them.
+    pub fn from_f32(charge: f32) -> Self {
+        Self::from_f64(f64::from(charge))
+    }
+
+    /// Creates a charge from an `f64`, rounded to the nearest 1e-7 e.
+    pub fn from_f64(charge: f64) -> Self {
+        let units = charge * Self::SCALE as f64;
+        // Round half away from zero without `f64::round`, which needs `std`.
+        let rounded = if units < 0.0 {
+            units - 0.5
+        } else {
+            units + 0.5
+        };
+        Self(rounded as i64)
+    }
+
+    /// Returns the charge as a number of 1e-7 e units.
+    pub const fn units(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the charge as the nearest `f32`.
+    ///
+    /// For tabulated charges this is the same value the `f32` lookups return.
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Returns the charge as the nearest `f64`.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::SCALE as f64
+    }
+
+    /// Returns `true` if the charge is a whole number of elementary charges.
+    pub const fn is_integer(self) -> bool {
+        self.0 % Self::SCALE == 0
+    }
+
+    /// Returns the nearest whole number of elementary charges, rounding halves away from
+    /// zero.
+    pub const fn round(self) -> Self {
+        let half = Self::SCALE / 2;
+        let units = if self.0 < 0 {
+            self.0 - half
+        } else {
+            self.0 + half
+        };
+        Self(units / Self::SCALE * Self::SCALE)
+    }
+
+    /// Returns the charge in elementary charges if it is a whole number.
+    pub const fn to_integer(self) -> Option<i64> {
+        if self.is_integer() {
+            Some(self.0 / Self::SCALE)
+        } else {
+            None
+        }
+    }
+}
+
+/// Net charge of a residue or chain, split into a whole number of elementary charges and
+/// the residual.
+///
+/// The formal charge is exactly integral. The residual is zero for integral sums and
+/// carries the deviation of published templates that are not, up to 1e-5 e per template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NetCharge {
+    /// Nearest whole number of elementary charges, rounding halves away from zero.
+    pub formal: i64,
+    /// Exact sum minus the formal charge.
+    pub residual: Charge,
+}
+
+impl NetCharge {
+    /// Returns the net charge of a chain of residue templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `templates` - Residue templates of the chain, in any order.
+    pub fn of<I>(templates: I) -> Self
+    where
+        I: IntoIterator<Item = crate::ResidueTemplate>,
+    {
+        templates
+            .into_iter()
+            .map(crate::ResidueTemplate::exact_net_charge)
+            .sum::<Charge>()
+            .into()
+    }
+
+    /// Returns `true` if the exact sum is the formal charge.
+    pub const fn is_integral(self) -> bool {
+        self.residual.0 == 0
+    }
+
+    /// Returns the exact sum of the formal charge and the residual.
+    pub const fn total(self) -> Charge {
+        Charge(self.formal * Charge::SCALE + self.residual.0)
+    }
+}
+
+impl From<Charge> for NetCharge {
+    fn from(charge: Charge) -> Self {
+        let formal = charge.round();
+        Self {
+            formal: formal.0 / Charge::SCALE,
+            residual: charge - formal,
+        }
+    }
+}
+
+impl From<Charge> for f32 {
+    fn from(charge: Charge) -> Self {
+        charge.to_f32()
+    }
+}
+
+impl From<Charge> for f64 {
+    fn from(charge: Charge) -> Self {
+        charge.to_f64()
+    }
+}
+
+impl Add for Charge {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Charge {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Charge {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl AddAssign for Charge {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Charge {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for Charge {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Charge> for Charge {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Formats the exact decimal value, without trailing zeros (e.g., "-0.4157", "-3").
+impl fmt::Display for Charge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let units = self.0.unsigned_abs();
+        let scale = Self::SCALE as u64;
+        let (int, mut frac) = (units / scale, units % scale);
+        if frac == 0 {
+            return write!(f, "{}{}", sign, int);
+        }
+        let mut digits = 7;
+        while frac % 10 == 0 {
+            frac /= 10;
+            digits -= 1;
+        }
+        write!(f, "{}{}.{:0width$}", sign, int, frac, width = digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Charge::from_f32(-0.4157).units(), -4_157_000);
+        assert_eq!(Charge::from_f64(0.0000001).units(), 1);
+        assert_eq!(Charge::from_f64(-0.00000015).units(), -2);
+        assert_eq!(Charge::from_integer(-3).units(), -30_000_000);
+        assert_eq!(Charge::from_units(-4_157_000).to_f32(), -0.4157);
+        assert_eq!(f64::from(Charge::from_units(5_000_000)), 0.5);
+        assert_eq!(f32::from(Charge::from_integer(2)), 2.0);
+    }
+
+    #[test]
+    fn exact_sums() {
+        let charges = [0.1, 0.2, -0.3].map(Charge::from_f64);
+        let total: Charge = charges.iter().sum();
+        assert_eq!(total, Charge::ZERO);
+        assert!(total.is_integer());
+
+        let mut q = Charge::from_f64(0.4242243);
+        q += Charge::from_f64(0.4242243);
+        q -= Charge::from_f64(0.8484486);
+        assert_eq!(q, Charge::ZERO);
+        assert_eq!(
+            -Charge::from_integer(1) + Charge::from_integer(3),
+            Charge::from_integer(2)
+        );
+        assert_eq!(
+            Charge::from_integer(1) - Charge::from_f64(0.5),
+            Charge::from_f64(0.5)
+        );
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(Charge::from_integer(-3).to_integer(), Some(-3));
+        assert_eq!(Charge::from_units(-29_999_847).to_integer(), None);
+        assert!(!Charge::from_f64(0.5).is_integer());
+        assert!(Charge::ZERO.is_integer());
+        assert_eq!(
+            Charge::from_units(-29_999_847).round(),
+            Charge::from_integer(-3)
+        );
+        assert_eq!(
+            Charge::from_units(10_000_002).round(),
+            Charge::from_integer(1)
+        );
+        assert_eq!(
+            Charge::from_units(-5_000_000).round(),
+            Charge::from_integer(-1)
+        );
+        assert_eq!(Charge::from_units(4_999_999).round(), Charge::ZERO);
+    }
+
+    #[test]
+    fn net_charge() {
+        let net = NetCharge::from(Charge::from_units(-29_999_847));
+        assert_eq!(net.formal, -3);
+        assert_eq!(net.residual, Charge::from_units(153));
+        assert!(!net.is_integral());
+        assert_eq!(net.total(), Charge::from_units(-29_999_847));
+
+        let net = NetCharge::from(Charge::from_integer(2));
+        assert_eq!(net.formal, 2);
+        assert!(net.is_integral());
+        assert_eq!(NetCharge::default().total(), Charge::ZERO);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Charge::from_units(-4_157_000).to_string(), "-0.4157");
+        assert_eq!(Charge::from_integer(-3).to_string(), "-3");
+        assert_eq!(Charge::from_units(1).to_string(), "0.0000001");
+        assert_eq!(Charge::from_units(15_000_000).to_string(), "1.5");
+        assert_eq!(Charge::ZERO.to_string(), "0");
+    }
+}
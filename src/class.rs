@@ -0,0 +1,77 @@
+//! Residue classification.
+
+/// Molecule class of a residue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResidueClass {
+    /// Amino acid residue.
+    Protein,
+    /// Deoxyribonucleotide residue.
+    Dna,
+    /// Ribonucleotide residue.
+    Rna,
+    /// Water (`HOH`).
+    Water,
+    /// Monatomic ion.
+    Ion,
+}
+
+impl ResidueClass {
+    /// Returns `true` for DNA and RNA residues.
+    pub const fn is_nucleic(self) -> bool {
+        matches!(self, Self::Dna | Self::Rna)
+    }
+}
+
+/// Returns the molecule class of a residue.
+///
+/// Classes are derived from the charge tables of the schemes compiled in: a residue is a
+/// protein or nucleic acid residue if any enabled scheme defines it at any position.
+///
+/// # Arguments
+///
+/// * `residue` - Residue name (e.g., "ALA", "DA", "HOH", "NA").
+///
+/// # Returns
+///
+/// `Option<ResidueClass>` - Molecule class if the residue is known, otherwise `None`
+/// (e.g., for ligands).
+pub fn classify(residue: &str) -> Option<ResidueClass> {
+    crate::generated::get_residue_class(residue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(
+        feature = "amber-ffsb",
+        feature = "amber-nucleic",
+        feature = "water-tip3p",
+        feature = "ions"
+    ))]
+    fn classes() {
+        assert_eq!(classify("ALA"), Some(ResidueClass::Protein));
+        assert_eq!(classify("HIP"), Some(ResidueClass::Protein));
+        assert_eq!(classify("DT"), Some(ResidueClass::Dna));
+        assert_eq!(classify("U"), Some(ResidueClass::Rna));
+        assert_eq!(classify("HOH"), Some(ResidueClass::Water));
+        assert_eq!(classify("NA"), Some(ResidueClass::Ion));
+        assert_eq!(classify("LIG"), None);
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "amber-nucleic", feature = "charmm")))]
+    fn disabled_classes() {
+        assert_eq!(classify("DT"), None);
+        assert_eq!(classify("U"), None);
+    }
+
+    #[test]
+    fn nucleic() {
+        assert!(ResidueClass::Dna.is_nucleic());
+        assert!(ResidueClass::Rna.is_nucleic());
+        assert!(!ResidueClass::Protein.is_nucleic());
+        assert!(!ResidueClass::Ion.is_nucleic());
+    }
+}
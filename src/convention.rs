@@ -0,0 +1,280 @@
+//! Residue naming conventions.
+
+use crate::{
+    IonScheme, NucleicPositionArg, NucleicScheme, Position, ProteinPositionArg, ProteinScheme,
+    ResidueTemplate, WaterCharges, WaterScheme,
+};
+
+/// Naming convention of the residues in an input structure.
+///
+/// Each convention maps its residue names onto the canonical names used by the charge
+/// tables (e.g., CHARMM `HSD` → `HID`, GROMACS `SOL` → `HOH`). Names that encode a
+/// chain position (e.g., AMBER `NALA`, `DA5`) also resolve to that [`Position`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Convention {
+    /// AMBER (tleap library names: `NALA`, `CALA`, `DA5`, `RA3`, `WAT`, `Na+`, ...).
+    Amber,
+    /// CHARMM (`HSD`, `HSE`, `HSP`, `ASPP`, `LSN`, `URA`, `TIP3`, `SOD`, `CLA`, ...).
+    ///
+    /// CHARMM names adenine, cytosine and guanine `ADE`, `CYT` and `GUA` in both DNA and
+    /// RNA, telling them apart only by a patch, so these names are not resolved; rename
+    /// them to `DA`/`A`, `DC`/`C` or `DG`/`G`. `THY` resolves to `DT` and `URA` to `U`.
+    Charmm,
+    /// GROMACS (`HISD`, `CYS2`, `LYSN`, `SOL`, `DA5`, ...).
+    Gromacs,
+    /// wwPDB chemical component names (`HIS`, `DOD`, ...).
+    Pdb,
+    /// PDB format v2.3 names (`1HB`, `O1P`, `H5'1`, `T`, ...).
+    PdbV2,
+}
+
+impl Convention {
+    /// Returns the internal key for this convention.
+    pub(crate) const fn key(self) -> &'static str {
+        match self {
+            Self::Amber => "amber",
+            Self::Charmm => "charmm",
+            Self::Gromacs => "gromacs",
+            Self::Pdb => "pdb",
+            Self::PdbV2 => "pdbv2",
+        }
+    }
+
+    /// Returns all available conventions.
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::Amber,
+            Self::Charmm,
+            Self::Gromacs,
+            Self::Pdb,
+            Self::PdbV2,
+        ]
+    }
+
+    /// Resolves a residue name to its canonical name and position.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Residue name in this convention (e.g., "HSD", "DA5").
+    /// * `pos` - Position of the residue in the chain.
+    ///
+    /// # Returns
+    ///
+    /// `(&str, Position)` - Canonical residue name and position. Names without an alias
+    /// are returned unchanged, and `pos` is kept unless the alias encodes a position.
+    pub fn residue(self, name: &str, pos: Position) -> (&str, Position) {
+        match crate::generated::get_residue_alias(self.key(), name) {
+            Some((residue, Some(alias_pos))) => (residue, alias_pos),
+            Some((residue, None)) => (residue, pos),
+            None => (name, pos),
+        }
+    }
+
+    /// Resolves an atom name to its canonical name.
+    ///
+    /// # Arguments
+    ///
+    /// * `residue` - Canonical residue name (e.g., "ILE"), as returned by [`Convention::residue`].
+    /// * `name` - Atom name in this convention (e.g., "HN", "CD", "1HB").
+    ///
+    /// # Returns
+    ///
+    /// `&str` - Canonical atom name. Residue-specific aliases take precedence over
+    /// aliases shared by all residues, and names without an alias are returned unchanged.
+    pub fn atom<'a>(self, residue: &str, name: &'a str) -> &'a str {
+        crate::generated::get_atom_alias(self.key(), residue, name).unwrap_or(name)
+    }
+}
+
+/// A charge scheme that accepts residue names in a given [`Convention`].
+///
+/// Created with the `with_convention` method of each scheme. Residue names are
+/// resolved with [`Convention::residue`] and atom names with [`Convention::atom`]
+/// before the lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aliased<S> {
+    scheme: S,
+    convention: Convention,
+}
+
+impl<S: Copy> Aliased<S> {
+    /// Creates an aliased lookup for a scheme.
+    pub const fn new(scheme: S, convention: Convention) -> Self {
+        Self { scheme, convention }
+    }
+
+    /// Returns the underlying scheme.
+    pub const fn scheme(self) -> S {
+        self.scheme
+    }
+
+    /// Returns the residue naming convention.
+    pub const fn convention(self) -> Convention {
+        self.convention
+    }
+}
+
+impl Aliased<ProteinScheme> {
+    /// Returns the partial charge for an atom in a protein residue.
+    ///
+    /// See [`ProteinScheme::charge`].
+    pub fn charge(self, pos: impl ProteinPositionArg, residue: &str, atom: &str) -> Option<f32> {
+        let (residue, pos) = self.convention.residue(residue, pos.into());
+        let atom = self.convention.atom(residue, atom);
+        self.scheme.charge(pos, residue, atom)
+    }
+
+    /// Returns the charge template for a protein residue.
+    ///
+    /// See [`ProteinScheme::residue`].
+    pub fn residue(self, pos: impl ProteinPositionArg, residue: &str) -> Option<ResidueTemplate> {
+        let (residue, pos) = self.convention.residue(residue, pos.into());
+        self.scheme.residue(pos, residue)
+    }
+}
+
+impl Aliased<NucleicScheme> {
+    /// Returns the partial charge for an atom in a nucleic acid residue.
+    ///
+    /// See [`NucleicScheme::charge`].
+    pub fn charge(self, pos: impl NucleicPositionArg, residue: &str, atom: &str) -> Option<f32> {
+        let (residue, pos) = self.convention.residue(residue, pos.into());
+        let atom = self.convention.atom(residue, atom);
+        self.scheme.charge(pos, residue, atom)
+    }
+
+    /// Returns the charge template for a nucleic acid residue.
+    ///
+    /// See [`NucleicScheme::residue`].
+    pub fn residue(self, pos: impl NucleicPositionArg, residue: &str) -> Option<ResidueTemplate> {
+        let (residue, pos) = self.convention.residue(residue, pos.into());
+        self.scheme.residue(pos, residue)
+    }
+}
+
+impl Aliased<WaterScheme> {
+    /// Returns the partial charges for a water residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `residue` - Residue name (e.g., "WAT", "SOL", "TIP3").
+    ///
+    /// # Returns
+    ///
+    /// `Option<WaterCharges>` - Partial charges if the residue is water, otherwise `None`.
+    pub fn charges(self, residue: &str) -> Option<WaterCharges> {
+        match self.convention.residue(residue, Position::Middle) {
+            ("HOH", _) => self.scheme.charges(),
+            _ => None,
+        }
+    }
+}
+
+impl Aliased<IonScheme> {
+    /// Returns the partial charge for an ion residue.
+    ///
+    /// See [`IonScheme::charge`].
+    pub fn charge(self, residue: &str) -> Option<f32> {
+        let (residue, _) = self.convention.residue(residue, Position::Middle);
+        self.scheme.charge(residue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_format() {
+        for c in Convention::all() {
+            let k = c.key();
+            assert!(
+                k.chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            );
+        }
+    }
+
+    #[test]
+    fn passthrough() {
+        for &c in Convention::all() {
+            assert_eq!(
+                c.residue("ALA", Position::Middle),
+                ("ALA", Position::Middle)
+            );
+            assert_eq!(
+                c.residue("XYZ", Position::CTerminal),
+                ("XYZ", Position::CTerminal)
+            );
+        }
+    }
+
+    #[test]
+    fn protonation_states() {
+        assert_eq!(Convention::Charmm.residue("HSP", Position::Middle).0, "HIP");
+        assert_eq!(
+            Convention::Charmm.residue("ASPP", Position::Middle).0,
+            "ASH"
+        );
+        assert_eq!(
+            Convention::Gromacs.residue("CYS2", Position::Middle).0,
+            "CYX"
+        );
+        assert_eq!(
+            Convention::Gromacs.residue("HISD", Position::Middle).0,
+            "HID"
+        );
+        assert_eq!(Convention::Pdb.residue("HIS", Position::Middle).0, "HIE");
+    }
+
+    #[test]
+    fn positions() {
+        assert_eq!(
+            Convention::Amber.residue("NALA", Position::Middle),
+            ("ALA", Position::NTerminal)
+        );
+        assert_eq!(
+            Convention::Amber.residue("RA3", Position::Middle),
+            ("A", Position::ThreePrime)
+        );
+        assert_eq!(
+            Convention::Gromacs.residue("DAN", Position::Middle),
+            ("DA", Position::FiveThreePrime)
+        );
+    }
+
+    #[test]
+    fn conventions_are_independent() {
+        assert_eq!(Convention::Pdb.residue("HSD", Position::Middle).0, "HSD");
+        assert_eq!(Convention::Charmm.residue("SOL", Position::Middle).0, "SOL");
+        assert_eq!(Convention::Pdb.atom("ALA", "1HB"), "1HB");
+        assert_eq!(Convention::Amber.atom("ALA", "HN"), "HN");
+    }
+
+    #[test]
+    fn atom_passthrough() {
+        for &c in Convention::all() {
+            assert_eq!(c.atom("ALA", "CA"), "CA");
+            assert_eq!(c.atom("XYZ", "QQ"), "QQ");
+        }
+    }
+
+    #[test]
+    fn atom_aliases() {
+        assert_eq!(Convention::Charmm.atom("ALA", "HN"), "H");
+        assert_eq!(Convention::Charmm.atom("GLY", "OT2"), "OXT");
+        assert_eq!(Convention::Charmm.atom("DA", "H5'1"), "H5'");
+        assert_eq!(Convention::Charmm.atom("ILE", "CD"), "CD1");
+        assert_eq!(Convention::Charmm.atom("SER", "HB1"), "HB2");
+        assert_eq!(Convention::Gromacs.atom("HOH", "OW"), "O");
+        assert_eq!(Convention::Amber.atom("DA", "O1P"), "OP1");
+        assert_eq!(Convention::PdbV2.atom("SER", "1HB"), "HB2");
+        assert_eq!(Convention::PdbV2.atom("DA", "1H5*"), "H5'");
+    }
+
+    #[test]
+    fn residue_specific_atom_aliases() {
+        assert_eq!(Convention::Charmm.atom("ALA", "HB1"), "HB1");
+        assert_eq!(Convention::Charmm.atom("ALA", "CD"), "CD");
+    }
+}
@@ -0,0 +1,327 @@
+//! Runtime loading of charge tables in the bundled CSV format.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use core::fmt;
+use std::io::{self, BufRead};
+
+use crate::{CustomScheme, Position};
+
+/// Columns required in the header row.
+const COLUMNS: [&str; 5] = ["scheme", "position", "residue", "atom", "charge"];
+
+/// Error returned when a charge table cannot be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvError {
+    /// One-based line number of the offending line.
+    pub line: usize,
+    /// Reason the line was rejected.
+    pub kind: CsvErrorKind,
+}
+
+/// Reason a charge table line was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CsvErrorKind {
+    /// The input has no header row.
+    MissingHeader,
+    /// The header row lacks a required column.
+    MissingColumn(&'static str),
+    /// The record has a different number of fields than the header.
+    FieldCount {
+        /// Number of columns in the header.
+        expected: usize,
+        /// Number of fields in the record.
+        found: usize,
+    },
+    /// A required field is empty.
+    EmptyField(&'static str),
+    /// The position key is not recognized (e.g., "n", "5", "m").
+    UnknownPosition(String),
+    /// The charge is not a finite number.
+    InvalidCharge(String),
+    /// The atom appears twice in the same residue template.
+    DuplicateAtom {
+        /// Residue name.
+        residue: String,
+        /// Atom name.
+        atom: String,
+    },
+    /// The input could not be read.
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for CsvErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "missing header row"),
+            Self::MissingColumn(column) => write!(f, "missing column '{}'", column),
+            Self::FieldCount { expected, found } => {
+                write!(f, "expected {} fields, found {}", expected, found)
+            }
+            Self::EmptyField(column) => write!(f, "empty '{}' field", column),
+            Self::UnknownPosition(key) => write!(f, "unknown position '{}'", key),
+            Self::InvalidCharge(value) => write!(f, "invalid charge '{}'", value),
+            Self::DuplicateAtom { residue, atom } => {
+                write!(f, "duplicate atom '{}' in residue '{}'", atom, residue)
+            }
+            Self::Io(kind) => write!(f, "{}", io::Error::from(*kind)),
+        }
+    }
+}
+
+impl core::error::Error for CsvError {}
+
+/// Line-by-line parser state.
+#[derive(Default)]
+struct Parser {
+    /// Field index of each required column, once the header has been read.
+    columns: Option<[usize; 5]>,
+    width: usize,
+    schemes: BTreeMap<String, CustomScheme>,
+}
+
+impl Parser {
+    fn line(&mut self, text: &str) -> Result<(), CsvErrorKind> {
+        if text.starts_with('#') || text.trim().is_empty() {
+            return Ok(());
+        }
+        let fields: std::vec::Vec<&str> = text.split(',').map(str::trim).collect();
+
+        let Some(columns) = self.columns else {
+            let mut columns = [0; 5];
+            for (index, name) in columns.iter_mut().zip(COLUMNS) {
+                *index = fields
+                    .iter()
+                    .position(|&f| f == name)
+                    .ok_or(CsvErrorKind::MissingColumn(name))?;
+            }
+            self.columns = Some(columns);
+            self.width = fields.len();
+            return Ok(());
+        };
+
+        if fields.len() != self.width {
+            return Err(CsvErrorKind::FieldCount {
+                expected: self.width,
+                found: fields.len(),
+            });
+        }
+        let [scheme, position, residue, atom, charge] = columns.map(|i| fields[i]);
+        for (value, name) in [(scheme, "scheme"), (residue, "residue"), (atom, "atom")] {
+            if value.is_empty() {
+                return Err(CsvErrorKind::EmptyField(name));
+            }
+        }
+
+        let position = match position {
+            "" => None,
+            key => Some(
+                Position::from_key(key)
+                    .ok_or_else(|| CsvErrorKind::UnknownPosition(key.to_string()))?,
+            ),
+        };
+        let charge = charge
+            .parse::<f32>()
+            .ok()
+            .filter(|c| c.is_finite())
+            .ok_or_else(|| CsvErrorKind::InvalidCharge(charge.to_string()))?;
+
+        if !self.schemes.contains_key(scheme) {
+            self.schemes.insert(scheme.to_string(), CustomScheme::new());
+        }
+        let atoms = self
+            .schemes
+            .get_mut(scheme)
+            .expect("scheme was just inserted")
+            .atoms_mut(position, residue);
+        if atoms.iter().any(|(a, _)| a == atom) {
+            return Err(CsvErrorKind::DuplicateAtom {
+                residue: residue.to_string(),
+                atom: atom.to_string(),
+            });
+        }
+        atoms.push((atom.to_string(), charge));
+        Ok(())
+    }
+
+    fn finish(self, lines: usize) -> Result<BTreeMap<String, CustomScheme>, CsvError> {
+        match self.columns {
+            Some(_) => Ok(self.schemes),
+            None => Err(CsvError {
+                line: lines + 1,
+                kind: CsvErrorKind::MissingHeader,
+            }),
+        }
+    }
+}
+
+impl CustomScheme {
+    /// Parses charge tables in the bundled CSV format.
+    ///
+    /// The input follows the layout of the crate's `data/*.csv` files: a header row with
+    /// `scheme`, `position`, `residue`, `atom` and `charge` columns, one atom per line,
+    /// lines starting with `#` ignored and whitespace around fields trimmed. Fields are
+    /// not quoted. An empty position (as used for water and ions) applies the template
+    /// at every position.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - CSV contents.
+    ///
+    /// # Returns
+    ///
+    /// `BTreeMap<String, CustomScheme>` - One scheme per value of the `scheme` column
+    /// (e.g., "amber-ffsb", "tip3p").
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CsvError`] with the line number of the first malformed line.
+    pub fn parse_csv(text: &str) -> Result<BTreeMap<String, CustomScheme>, CsvError> {
+        let mut parser = Parser::default();
+        let mut lines = 0;
+        for (i, line) in text.lines().enumerate() {
+            parser
+                .line(line)
+                .map_err(|kind| CsvError { line: i + 1, kind })?;
+            lines = i + 1;
+        }
+        parser.finish(lines)
+    }
+
+    /// Reads charge tables in the bundled CSV format.
+    ///
+    /// See [`CustomScheme::parse_csv`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CsvError`] with the line number of the first malformed or unreadable
+    /// line.
+    pub fn read_csv<R: io::Read>(reader: R) -> Result<BTreeMap<String, CustomScheme>, CsvError> {
+        let mut parser = Parser::default();
+        let mut lines = 0;
+        for (i, line) in io::BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| CsvError {
+                line: i + 1,
+                kind: CsvErrorKind::Io(e.kind()),
+            })?;
+            parser
+                .line(&line)
+                .map_err(|kind| CsvError { line: i + 1, kind })?;
+            lines = i + 1;
+        }
+        parser.finish(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> CsvError {
+        CustomScheme::parse_csv(text).unwrap_err()
+    }
+
+    #[test]
+    fn comments_and_trimming() {
+        let schemes = CustomScheme::parse_csv(
+            "# leading comment\n\
+             scheme, position ,residue,atom,charge\n\
+             \n\
+             # another\n\
+             ff , m , LIG , C1 , 0.25\n\
+             ff,m,LIG,O1,-0.25\r\n",
+        )
+        .unwrap();
+        let ff = &schemes["ff"];
+        assert_eq!(ff.charge(Position::Middle, "LIG", "C1"), Some(0.25));
+        assert_eq!(ff.charge(Position::Middle, "LIG", "O1"), Some(-0.25));
+        assert_eq!(ff.len(), 1);
+    }
+
+    #[test]
+    fn column_order() {
+        let schemes =
+            CustomScheme::parse_csv("charge,atom,residue,position,scheme\n1.0,NA,NA,,ion\n")
+                .unwrap();
+        assert_eq!(
+            schemes["ion"].charge(Position::NTerminal, "NA", "NA"),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error("# only comments\n").kind, CsvErrorKind::MissingHeader);
+        assert_eq!(
+            error("scheme,position,residue,atom\n").kind,
+            CsvErrorKind::MissingColumn("charge")
+        );
+        let header = "scheme,position,residue,atom,charge\n";
+        let e = error(&[header, "ff,m,LIG,C1\n"].concat());
+        assert_eq!(e.line, 2);
+        assert_eq!(
+            e.kind,
+            CsvErrorKind::FieldCount {
+                expected: 5,
+                found: 4
+            }
+        );
+        assert_eq!(
+            error(&[header, "ff,m,,C1,0.0\n"].concat()).kind,
+            CsvErrorKind::EmptyField("residue")
+        );
+        assert_eq!(
+            error(&[header, "ff,x,LIG,C1,0.0\n"].concat()).kind,
+            CsvErrorKind::UnknownPosition("x".into())
+        );
+        assert_eq!(
+            error(&[header, "ff,m,LIG,C1,abc\n"].concat()).kind,
+            CsvErrorKind::InvalidCharge("abc".into())
+        );
+        for value in ["nan", "inf", "-inf"] {
+            let e = error(&[header, "ff,m,LIG,C1,0.0\n", "ff,m,LIG,O1,", value, "\n"].concat());
+            assert_eq!(e.line, 3);
+            assert_eq!(e.kind, CsvErrorKind::InvalidCharge(value.into()));
+        }
+        let e = error(&[header, "ff,m,LIG,C1,0.0\n# c\nff,m,LIG,C1,0.0\n"].concat());
+        assert_eq!(e.line, 4);
+        assert_eq!(
+            e.kind,
+            CsvErrorKind::DuplicateAtom {
+                residue: "LIG".into(),
+                atom: "C1".into()
+            }
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            error("scheme,position,residue,atom,charge\nff,m,LIG,C1,q\n").to_string(),
+            "line 2: invalid charge 'q'"
+        );
+    }
+
+    #[test]
+    fn read_matches_parse() {
+        let text = "scheme,position,residue,atom,charge\nff,n,LIG,C1,0.5\n";
+        assert_eq!(
+            CustomScheme::read_csv(text.as_bytes()).unwrap(),
+            CustomScheme::parse_csv(text).unwrap()
+        );
+        assert_eq!(
+            CustomScheme::read_csv(&[0xff, b'\n'][..]).unwrap_err(),
+            CsvError {
+                line: 1,
+                kind: CsvErrorKind::Io(io::ErrorKind::InvalidData)
+            }
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! User-defined charge schemes.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Position, ResidueAtoms, ResidueChargeProvider};
+
+/// Atom templates of one residue, keyed by position (`None` for any position).
+type Templates = BTreeMap<Option<Position>, Vec<(String, f32)>>;
+
+/// Residue templates owned at runtime.
+///
+/// Templates are keyed by residue name and position; templates without a position
+/// (e.g., water and ions) apply at every position that has no template of its own.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CustomScheme {
+    templates: BTreeMap<String, Templates>,
+}
+
+impl CustomScheme {
+    /// Creates an empty scheme.
+    pub const fn new() -> Self {
+        Self {
+            templates: BTreeMap::new(),
+        }
+    }
+
+    /// Adds or replaces a residue template.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    /// * `atoms` - Atom names and partial charges, in force field order.
+    ///
+    /// # Returns
+    ///
+    /// `bool` - `true` if a template for the residue and position was replaced.
+    pub fn insert<R, A, I>(&mut self, pos: Position, residue: R, atoms: I) -> bool
+    where
+        R: Into<String>,
+        A: Into<String>,
+        I: IntoIterator<Item = (A, f32)>,
+    {
+        let atoms = atoms.into_iter().map(|(a, c)| (a.into(), c)).collect();
+        self.insert_at(Some(pos), residue.into(), atoms)
+    }
+
+    /// Adds or replaces a template under a position key (`None` for any position).
+    pub(crate) fn insert_at(
+        &mut self,
+        pos: Option<Position>,
+        residue: String,
+        atoms: Vec<(String, f32)>,
+    ) -> bool {
+        self.templates
+            .entry(residue)
+            .or_default()
+            .insert(pos, atoms)
+            .is_some()
+    }
+
+    /// Returns the atoms of a template under a position key for in-place extension.
+    #[cfg(feature = "std")]
+    pub(crate) fn atoms_mut(
+        &mut self,
+        pos: Option<Position>,
+        residue: &str,
+    ) -> &mut Vec<(String, f32)> {
+        if !self.templates.contains_key(residue) {
+            self.templates.insert(residue.into(), BTreeMap::new());
+        }
+        self.templates
+            .get_mut(residue)
+            .expect("residue was just inserted")
+            .entry(pos)
+            .or_default()
+    }
+
+    /// Removes a residue template.
+    ///
+    /// # Returns
+    ///
+    /// `bool` - `true` if a template was removed.
+    pub fn remove(&mut self, pos: Position, residue: &str) -> bool {
+        let Some(positions) = self.templates.get_mut(residue) else {
+            return false;
+        };
+        let removed = positions.remove(&Some(pos)).is_some();
+        if positions.is_empty() {
+            self.templates.remove(residue);
+        }
+        removed
+    }
+
+    /// Returns the number of residue templates.
+    pub fn len(&self) -> usize {
+        self.templates.values().map(BTreeMap::len).sum()
+    }
+
+    /// Returns `true` if the scheme has no residue templates.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Returns the names of all residues with at least one template, in sorted order.
+    pub fn residues(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    fn template(&self, pos: Position, residue: &str) -> Option<&[(String, f32)]> {
+        let positions = self.templates.get(residue)?;
+        positions
+            .get(&Some(pos))
+            .or_else(|| positions.get(&None))
+            .map(Vec::as_slice)
+    }
+
+    /// Returns the partial charge for an atom in a residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    /// * `atom` - Atom name (e.g., "C1", "CA").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if found, otherwise `None`.
+    pub fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        self.template(pos, residue)?
+            .iter()
+            .find(|(a, _)| a == atom)
+            .map(|(_, c)| *c)
+    }
+
+    /// Returns the atoms and partial charges of a residue, in force field order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    ///
+    /// # Returns
+    ///
+    /// `Option<ResidueAtoms>` - Atom iterator if the residue is defined, otherwise `None`.
+    pub fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        self.template(pos, residue).map(ResidueAtoms::from)
+    }
+
+    /// Returns `true` if the scheme defines the residue at the position.
+    pub fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        self.template(pos, residue).is_some()
+    }
+}
+
+impl ResidueChargeProvider for CustomScheme {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        CustomScheme::charge(self, pos, residue, atom)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        CustomScheme::residue_atoms(self, pos, residue)
+    }
+
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        CustomScheme::contains_residue(self, pos, residue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut s = CustomScheme::new();
+        assert!(s.is_empty());
+        assert!(!s.insert(Position::Middle, "LIG", [("C1", 0.5), ("O1", -0.5)]));
+        assert!(s.insert(Position::Middle, "LIG", [("C1", 0.25)]));
+        assert!(!s.insert(Position::NTerminal, "LIG", [("C1", 0.0)]));
+        assert_eq!(s.len(), 2);
+        assert!(s.residues().eq(["LIG"]));
+        assert!(s.remove(Position::Middle, "LIG"));
+        assert!(!s.remove(Position::Middle, "LIG"));
+        assert!(s.remove(Position::NTerminal, "LIG"));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn any_position() {
+        let mut s = CustomScheme::new();
+        s.insert_at(None, "HOH".into(), alloc::vec![("O".into(), -0.8)]);
+        s.insert(Position::Middle, "HOH", [("O", -0.9)]);
+        assert_eq!(s.charge(Position::Middle, "HOH", "O"), Some(-0.9));
+        assert_eq!(s.charge(Position::FivePrime, "HOH", "O"), Some(-0.8));
+        assert_eq!(s.charge(Position::FivePrime, "HOH", "H1"), None);
+    }
+}
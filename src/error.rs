@@ -0,0 +1,68 @@
+//! Charge lookup errors.
+
+use core::fmt;
+
+use crate::Position;
+
+/// Reason a charge lookup failed.
+///
+/// Returned by the `try_*` lookup methods of the scheme enums. Borrowed names refer
+/// to the arguments of the failed lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChargeError<'a> {
+    /// The scheme has no residue data for this position.
+    UnsupportedPosition(Position),
+    /// The residue is not defined by the scheme at the requested position.
+    UnknownResidue(&'a str),
+    /// The residue is defined, but does not contain the atom.
+    UnknownAtom {
+        /// Residue name.
+        residue: &'a str,
+        /// Atom name.
+        atom: &'a str,
+    },
+}
+
+impl fmt::Display for ChargeError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedPosition(pos) => {
+                write!(f, "position {:?} is not supported by this scheme", pos)
+            }
+            Self::UnknownResidue(residue) => write!(f, "unknown residue '{}'", residue),
+            Self::UnknownAtom { residue, atom } => {
+                write!(f, "unknown atom '{}' in residue '{}'", atom, residue)
+            }
+        }
+    }
+}
+
+impl core::error::Error for ChargeError<'_> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ChargeError::UnsupportedPosition(Position::FivePrime).to_string(),
+            "position FivePrime is not supported by this scheme"
+        );
+        assert_eq!(
+            ChargeError::UnknownResidue("XYZ").to_string(),
+            "unknown residue 'XYZ'"
+        );
+        assert_eq!(
+            ChargeError::UnknownAtom {
+                residue: "ALA",
+                atom: "CZ"
+            }
+            .to_string(),
+            "unknown atom 'CZ' in residue 'ALA'"
+        );
+    }
+}
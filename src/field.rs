@@ -0,0 +1,33 @@
+//! Fixed-width record fields.
+
+/// Converts a padded byte column to a name.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw column bytes (e.g., `b" CA "`).
+///
+/// # Returns
+///
+/// `Option<&str>` - Name with leading and trailing ASCII whitespace removed, or `None`
+/// if the remaining bytes are not valid UTF-8.
+pub(crate) fn name(bytes: &[u8]) -> Option<&str> {
+    core::str::from_utf8(bytes.trim_ascii()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_padding() {
+        assert_eq!(name(b" CA "), Some("CA"));
+        assert_eq!(name(b"ALA"), Some("ALA"));
+        assert_eq!(name(b"  O5'"), Some("O5'"));
+        assert_eq!(name(b"    "), Some(""));
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        assert_eq!(name(b" C\xffA "), None);
+    }
+}
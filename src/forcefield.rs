@@ -0,0 +1,174 @@
+//! Force field bundles across molecule classes.
+
+use crate::{IonScheme, NucleicScheme, Position, ProteinScheme, ResidueClass, WaterScheme};
+
+/// Charge schemes for every molecule class of a system.
+///
+/// Routes each lookup to the scheme of the residue's [`ResidueClass`]: proteins, nucleic
+/// acids, water (`HOH`) and ions. The default is AMBER ff14SB with TIP3P water.
+///
+/// Presets and `Default` are only available when the cargo features of all their schemes
+/// are enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(
+        feature = "amber-ffsb",
+        feature = "amber-nucleic",
+        feature = "water-tip3p",
+        feature = "ions"
+    ),
+    derive(Default)
+)]
+pub struct ForceField {
+    /// Scheme for amino acid residues.
+    pub protein: ProteinScheme,
+    /// Scheme for nucleotide residues.
+    pub nucleic: NucleicScheme,
+    /// Water model for `HOH` residues.
+    pub water: WaterScheme,
+    /// Scheme for ion residues.
+    pub ion: IonScheme,
+}
+
+impl ForceField {
+    /// Creates a force field from one scheme per molecule class.
+    pub const fn new(
+        protein: ProteinScheme,
+        nucleic: NucleicScheme,
+        water: WaterScheme,
+        ion: IonScheme,
+    ) -> Self {
+        Self {
+            protein,
+            nucleic,
+            water,
+            ion,
+        }
+    }
+
+    /// Returns AMBER ff14SB proteins and OL nucleic acids with TIP3P water.
+    #[cfg(all(
+        feature = "amber-ffsb",
+        feature = "amber-nucleic",
+        feature = "water-tip3p",
+        feature = "ions"
+    ))]
+    pub const fn amber_ff14sb_tip3p() -> Self {
+        Self::new(
+            ProteinScheme::AmberFFSB,
+            NucleicScheme::Amber,
+            WaterScheme::Tip3p,
+            IonScheme::Classic,
+        )
+    }
+
+    /// Returns AMBER ff03 proteins and OL nucleic acids with TIP3P water.
+    #[cfg(all(
+        feature = "amber-ff03",
+        feature = "amber-nucleic",
+        feature = "water-tip3p",
+        feature = "ions"
+    ))]
+    pub const fn amber_ff03_tip3p() -> Self {
+        Self::new(
+            ProteinScheme::AmberFF03,
+            NucleicScheme::Amber,
+            WaterScheme::Tip3p,
+            IonScheme::Classic,
+        )
+    }
+
+    /// Returns CHARMM36m proteins and CHARMM36 nucleic acids with CHARMM TIP3P water.
+    #[cfg(all(feature = "charmm", feature = "water-tip3p", feature = "ions"))]
+    pub const fn charmm36m() -> Self {
+        Self::new(
+            ProteinScheme::Charmm,
+            NucleicScheme::Charmm,
+            WaterScheme::Tip3p,
+            IonScheme::Classic,
+        )
+    }
+
+    /// Returns the partial charge for an atom of any supported residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain (ignored for water and ions).
+    /// * `residue` - Residue name (e.g., "ALA", "DA", "HOH", "NA").
+    /// * `atom` - Atom name (e.g., "CA", "P", "O", "NA").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if found, otherwise `None`.
+    pub fn charge(self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        match crate::classify(residue)? {
+            ResidueClass::Protein => self.protein.charge(pos, residue, atom),
+            ResidueClass::Dna | ResidueClass::Rna => self.nucleic.charge(pos, residue, atom),
+            ResidueClass::Water => self.water.charges()?.charge(atom),
+            ResidueClass::Ion => self.ion.atom_charge(residue, atom),
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        all(
+            feature = "amber-ffsb",
+            feature = "amber-nucleic",
+            feature = "water-tip3p",
+            feature = "ions"
+        ),
+        all(feature = "charmm", feature = "water-tip3p", feature = "ions")
+    )
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(
+        feature = "amber-ffsb",
+        feature = "amber-nucleic",
+        feature = "water-tip3p",
+        feature = "ions"
+    ))]
+    fn default_preset() {
+        assert_eq!(ForceField::default(), ForceField::amber_ff14sb_tip3p());
+    }
+
+    #[test]
+    #[cfg(all(feature = "charmm", feature = "water-tip3p", feature = "ions"))]
+    fn routes_by_class() {
+        let ff = ForceField::charmm36m();
+        assert_eq!(
+            ff.charge(Position::Middle, "ALA", "CA"),
+            ProteinScheme::Charmm.charge(Position::Middle, "ALA", "CA")
+        );
+        assert_eq!(
+            ff.charge(Position::FivePrime, "DA", "N9"),
+            NucleicScheme::Charmm.charge(Position::FivePrime, "DA", "N9")
+        );
+        assert_eq!(
+            ff.charge(Position::Middle, "HOH", "H2"),
+            WaterScheme::Tip3p.charges().map(|w| w.h2)
+        );
+        assert_eq!(ff.charge(Position::Middle, "CL", "CL"), Some(-1.0));
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "amber-ffsb",
+        feature = "amber-nucleic",
+        feature = "water-tip3p",
+        feature = "ions"
+    ))]
+    fn unknown_atom_does_not_fall_through() {
+        let ff = ForceField::default();
+        assert_eq!(ff.charge(Position::Middle, "ALA", "NA"), None);
+        assert_eq!(ff.charge(Position::Middle, "HOH", "OW"), None);
+        assert_eq!(ff.charge(Position::Middle, "LIG", "C1"), None);
+        assert_eq!(ff.charge(Position::Middle, "NA", "XX"), None);
+        assert_eq!(ff.charge(Position::Middle, "IOD", "IOD"), None);
+        assert_eq!(ff.charge(Position::Middle, "IOD", "I"), Some(-1.0));
+    }
+}
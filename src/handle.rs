@@ -0,0 +1,176 @@
+//! Pre-resolved residue handles.
+
+use crate::generated::{ResidueEntry, get_residue_entry};
+use crate::{Charge, ResidueTemplate};
+
+/// Pre-resolved reference to a residue template.
+///
+/// Obtained from [`ProteinScheme::resolve`](crate::ProteinScheme::resolve) and
+/// [`NucleicScheme::resolve`](crate::NucleicScheme::resolve). A handle is a small integer
+/// index into the compiled charge tables, so lookups through it skip resolving the
+/// scheme, position and residue name again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResidueHandle(u16);
+
+impl ResidueHandle {
+    /// Creates a handle from a generated residue table index.
+    pub(crate) const fn new(id: u16) -> Self {
+        Self(id)
+    }
+
+    /// Returns the table index of the residue.
+    ///
+    /// Handles of the same template compare equal; the index is stable within a build
+    /// of the crate only.
+    pub const fn id(self) -> u16 {
+        self.0
+    }
+
+    fn entry(self) -> &'static ResidueEntry {
+        get_residue_entry(self.0)
+    }
+
+    /// Returns the index of an atom in the residue template.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name (e.g., "CA", "HB1").
+    ///
+    /// # Returns
+    ///
+    /// `Option<usize>` - Position of the atom in force field order, for use with
+    /// [`ResidueHandle::charge_by_id`], or `None` if the residue has no such atom.
+    pub fn atom_index(self, atom: &str) -> Option<usize> {
+        self.entry().atom_index(atom)
+    }
+
+    /// Returns the index of an atom in the residue template, from a raw byte column.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name column (e.g., `b" CA "`); surrounding ASCII whitespace is
+    ///   ignored.
+    ///
+    /// # Returns
+    ///
+    /// `Option<usize>` - Position of the atom in force field order, or `None` if the
+    /// residue has no such atom or the name is not valid UTF-8.
+    pub fn atom_index_bytes(self, atom: &[u8]) -> Option<usize> {
+        self.atom_index(crate::field::name(atom)?)
+    }
+
+    /// Returns the partial charge of an atom by its index.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom index, as returned by [`ResidueHandle::atom_index`].
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if the index is in range, otherwise `None`.
+    pub fn charge_by_id(self, atom: usize) -> Option<f32> {
+        self.entry().charge_at(atom)
+    }
+
+    /// Returns the partial charge of an atom by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name (e.g., "CA", "HB1").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if the atom exists, otherwise `None`.
+    pub fn charge(self, atom: &str) -> Option<f32> {
+        self.entry().charge(atom)
+    }
+
+    /// Returns the partial charge of an atom from a raw byte column.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name column (e.g., `b" CA "`); surrounding ASCII whitespace is
+    ///   ignored.
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if the atom exists, otherwise `None` (also for
+    /// names that are not valid UTF-8).
+    pub fn charge_bytes(self, atom: &[u8]) -> Option<f32> {
+        self.charge(crate::field::name(atom)?)
+    }
+
+    /// Returns the exact partial charge of an atom by its index.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom index, as returned by [`ResidueHandle::atom_index`].
+    ///
+    /// # Returns
+    ///
+    /// `Option<Charge>` - Exact partial charge if the index is in range, otherwise `None`.
+    pub fn exact_charge_by_id(self, atom: usize) -> Option<Charge> {
+        let &units = self.entry().charges().get(atom)?;
+        Some(Charge::from_units(units.into()))
+    }
+
+    /// Returns the exact partial charge of an atom by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name (e.g., "CA", "HB1").
+    ///
+    /// # Returns
+    ///
+    /// `Option<Charge>` - Exact partial charge if the atom exists, otherwise `None`.
+    pub fn exact_charge(self, atom: &str) -> Option<Charge> {
+        self.exact_charge_by_id(self.atom_index(atom)?)
+    }
+
+    /// Returns the number of atoms in the residue.
+    pub fn len(self) -> usize {
+        self.entry().len()
+    }
+
+    /// Returns `true` if the residue has no atoms.
+    pub fn is_empty(self) -> bool {
+        self.entry().len() == 0
+    }
+
+    /// Returns the charge template of the residue.
+    pub fn template(self) -> ResidueTemplate {
+        self.entry().template()
+    }
+}
+
+#[cfg(all(test, any(feature = "amber-ffsb", feature = "charmm")))]
+mod tests {
+    use crate::{Position, ProteinScheme};
+
+    #[test]
+    #[cfg(feature = "amber-ffsb")]
+    fn atom_ids_follow_template_order() {
+        let h = ProteinScheme::AmberFFSB
+            .resolve(Position::Middle, "ALA")
+            .expect("Missing: AmberFFSB/m/ALA");
+        for (i, (atom, charge)) in h.template().into_iter().enumerate() {
+            assert_eq!(h.atom_index(atom), Some(i));
+            assert_eq!(h.charge_by_id(i), Some(charge));
+            assert_eq!(h.exact_charge_by_id(i).map(|q| q.to_f32()), Some(charge));
+        }
+        assert_eq!(h.charge_by_id(h.len()), None);
+        assert_eq!(h.exact_charge_by_id(h.len()), None);
+        assert_eq!(h.exact_charge("XX"), None);
+        assert_eq!(h.atom_index("XX"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "charmm")]
+    fn ids_are_distinct() {
+        let scheme = ProteinScheme::Charmm;
+        let m = scheme.resolve(Position::Middle, "GLY").unwrap();
+        let n = scheme.resolve(Position::NTerminal, "GLY").unwrap();
+        assert_ne!(m.id(), n.id());
+        assert_eq!(scheme.resolve(Position::Middle, "GLY"), Some(m));
+    }
+}
@@ -14,8 +14,31 @@
 //!   charge retrieval.
 //! - **Comprehensive Coverage**: Supports 29 protein residues, 10 nucleic acid residues,
 //!   5 water models, and 66 ion types.
+//! - **Residue Templates**: Full ordered atom lists with charges via [`ResidueTemplate`].
 //! - **Terminal-Aware**: Handles N-/C-terminal protein residues (including protonation
-//!   variants) and 5'/3'-terminal nucleic acids.
+//!   variants), 5'/3'-terminal nucleic acids, and, with the `derived-templates` feature,
+//!   free amino acids and nucleosides.
+//! - **Naming Conventions**: Resolves AMBER, CHARMM, GROMACS and PDB residue names via
+//!   [`Convention`].
+//! - **Residue Classification**: Tells proteins, DNA, RNA, water and ions apart via
+//!   [`classify`].
+//! - **Force Field Bundles**: Routes any residue to its class scheme via [`ForceField`].
+//! - **Build-Time Data**: Compiles user CSV files listed in `FFCHARGE_EXTRA_DATA` into
+//!   the built-in schemes and [`ExtraScheme`].
+//! - **Exact Charges**: Sums table charges as fixed-point [`Charge`] values, so residue
+//!   and chain totals carry no float drift; [`NetCharge`] splits them into the formal
+//!   integer charge and the residual.
+//! - **Generic Providers**: Any charge source, including user-defined ones, can implement
+//!   [`ResidueChargeProvider`] and be chained with fallbacks.
+//! - **Custom Templates**: Overlay user residue templates on any provider with
+//!   `ChargeRegistry` (requires the `alloc` feature).
+//! - **Runtime Tables**: Load charge tables in the bundled CSV format into a
+//!   `CustomScheme` (requires the `std` feature).
+//! - **Tolerant Input**: Accepts mixed-case, whitespace-padded names via [`Normalized`].
+//! - **Compact Tables**: Shrinks the static residue tables to about a third with the
+//!   `compact` feature, returning the same `f32` charges.
+//! - **Selectable Schemes**: Each scheme is behind its own cargo feature (e.g.,
+//!   `amber-ffsb`, `water-opc3`, `ions`), so unused tables are not compiled in.
 //! - **Type-Safe API**: Strongly-typed enums for schemes and positions prevent invalid
 //!   queries at compile time.
 //! - **`no_std` Compatible**: Suitable for embedded systems and WebAssembly targets.
@@ -23,6 +46,13 @@
 //! ## Quick Start
 //!
 //! ```rust
+//! # #[cfg(all(
+//! #     feature = "amber-ffsb",
+//! #     feature = "amber-nucleic",
+//! #     feature = "water-tip3p",
+//! #     feature = "ions"
+//! # ))]
+//! # {
 //! use ffcharge::{ProteinScheme, NucleicScheme, WaterScheme, IonScheme, Position};
 //!
 //! // Protein: Get charge for CA atom of Alanine (middle position, AMBER ff99SB)
@@ -51,6 +81,7 @@
 //! // Ion: Sodium
 //! let na = IonScheme::Classic.charge("NA").expect("Ion not found");
 //! assert!((na - 1.0).abs() < 1e-6);
+//! # }
 //! ```
 //!
 //! ## Supported Force Fields
@@ -73,17 +104,98 @@
 //! | `NTerminalDeprotonated` | N-terminal (neutral NH₂) | Protein |
 //! | `CTerminal` | C-terminal (deprotonated COO⁻) | Protein |
 //! | `CTerminalProtonated` | C-terminal (protonated COOH) | Protein |
+//! | `NCTerminal` | Free amino acid (NH₃⁺ and COO⁻), derived | Protein |
+//! | `NCTerminalNeutral` | Free amino acid (NH₂ and COOH), derived | Protein |
 //! | `FivePrime` | 5'-terminal | Nucleic acid |
 //! | `ThreePrime` | 3'-terminal | Nucleic acid |
+//! | `FiveThreePrime` | Nucleoside without phosphate (5'- and 3'-terminal), derived | Nucleic acid |
 //! | `Middle` | Internal residue (default) | Both |
+//!
+//! Derived templates are built from the terminal templates of each scheme, not taken
+//! from published force field parameters, and are only compiled in with the
+//! `derived-templates` feature.
+//!
+//! [`ProteinPosition`] and [`NucleicPosition`] restrict these to a single molecule class.
+//! Scheme lookups accept either the general [`Position`] or the matching typed position,
+//! so mixing classes is rejected at compile time:
+//!
+//! ```rust
+//! # #[cfg(all(feature = "amber-ffsb", feature = "amber-nucleic"))]
+//! # {
+//! use ffcharge::{NucleicPosition, NucleicScheme, ProteinPosition, ProteinScheme};
+//!
+//! let ca = ProteinScheme::AmberFFSB.charge(ProteinPosition::CTerminal, "ALA", "CA");
+//! let p = NucleicScheme::Amber.charge(NucleicPosition::Middle, "DA", "P");
+//! assert!(ca.is_some() && p.is_some());
+//! # }
+//! ```
+//!
+//! ```compile_fail
+//! use ffcharge::{NucleicPosition, ProteinScheme};
+//!
+//! ProteinScheme::AmberFFSB.charge(NucleicPosition::FivePrime, "ALA", "CA");
+//! ```
+//!
+//! ## Scheme Features
+//!
+//! Each scheme enum variant is compiled in by its own cargo feature (e.g., `amber-ffsb`,
+//! `charmm`, `water-tip3p`, `ions`), and `all-schemes` enables them all by default.
+//! Disabling a feature removes the variant and its tables. Because cargo unifies
+//! features, another crate in the dependency graph may enable more variants than yours
+//! does, so the scheme enums are `#[non_exhaustive]` and matches on them need a wildcard
+//! arm.
 
 #![no_std]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod assign;
+mod charge;
+mod class;
+mod convention;
+#[cfg(feature = "std")]
+mod csv;
+#[cfg(feature = "alloc")]
+mod custom;
+mod error;
+mod field;
+mod forcefield;
+mod handle;
+mod normalize;
 mod position;
+mod provider;
+#[cfg(feature = "alloc")]
+mod registry;
+mod residue;
 mod scheme;
 
-pub use position::Position;
-pub use scheme::{IonScheme, NucleicScheme, ProteinScheme, WaterScheme};
+pub use assign::{AssignReport, AtomKey};
+pub use charge::{Charge, NetCharge};
+pub use class::{ResidueClass, classify};
+pub use convention::{Aliased, Convention};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use csv::{CsvError, CsvErrorKind};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use custom::CustomScheme;
+pub use error::ChargeError;
+pub use forcefield::ForceField;
+pub use handle::ResidueHandle;
+pub use normalize::Normalized;
+pub use position::{
+    NucleicPosition, NucleicPositionArg, Position, ProteinPosition, ProteinPositionArg,
+};
+pub use provider::{Fallback, ResidueAtoms, ResidueChargeProvider};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use registry::ChargeRegistry;
+pub use residue::ResidueTemplate;
+pub use scheme::{ExtraScheme, IonScheme, NucleicScheme, ProteinScheme, WaterScheme};
 
 /// Water charge distribution.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -96,6 +208,26 @@ pub struct WaterCharges {
     pub h2: f32,
 }
 
+impl WaterCharges {
+    /// Returns the partial charge of a water atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name ("O", "H1" or "H2").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if the atom is part of water, otherwise `None`.
+    pub fn charge(&self, atom: &str) -> Option<f32> {
+        match atom {
+            "O" => Some(self.o),
+            "H1" => Some(self.h1),
+            "H2" => Some(self.h2),
+            _ => None,
+        }
+    }
+}
+
 mod generated {
     include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 }
@@ -0,0 +1,156 @@
+//! Case- and whitespace-tolerant lookups.
+
+use crate::generated::MAX_NAME_LEN;
+use crate::{
+    IonScheme, NucleicPositionArg, NucleicScheme, ProteinPositionArg, ProteinScheme, ResidueHandle,
+    ResidueTemplate, WaterCharges, WaterScheme,
+};
+
+/// Normalizes a name into a stack buffer.
+///
+/// # Arguments
+///
+/// * `name` - Name in any letter case, possibly padded with whitespace (e.g., " hid ").
+/// * `buf` - Buffer receiving the normalized name.
+///
+/// # Returns
+///
+/// `Option<&str>` - Trimmed, ASCII-uppercased name, or `None` if it is longer than any
+/// name in the charge tables.
+fn normalize<'b>(name: &str, buf: &'b mut [u8; MAX_NAME_LEN]) -> Option<&'b str> {
+    let name = name.trim().as_bytes();
+    let out = buf.get_mut(..name.len())?;
+    for (o, b) in out.iter_mut().zip(name) {
+        *o = b.to_ascii_uppercase();
+    }
+    core::str::from_utf8(out).ok()
+}
+
+/// A charge scheme that accepts residue and atom names in any letter case.
+///
+/// Created with the `normalized` method of each scheme. Names are trimmed of
+/// surrounding whitespace and ASCII-uppercased on the stack before the lookup, so
+/// `" Ala"`, `"ala"` and `"ALA"` resolve to the same residue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Normalized<S> {
+    scheme: S,
+}
+
+impl<S: Copy> Normalized<S> {
+    /// Creates a normalized lookup for a scheme.
+    pub const fn new(scheme: S) -> Self {
+        Self { scheme }
+    }
+
+    /// Returns the underlying scheme.
+    pub const fn scheme(self) -> S {
+        self.scheme
+    }
+}
+
+impl Normalized<ProteinScheme> {
+    /// Returns the partial charge for an atom in a protein residue.
+    ///
+    /// See [`ProteinScheme::charge`].
+    pub fn charge(self, pos: impl ProteinPositionArg, residue: &str, atom: &str) -> Option<f32> {
+        let mut buf = [0; MAX_NAME_LEN];
+        self.resolve(pos, residue)?
+            .charge(normalize(atom, &mut buf)?)
+    }
+
+    /// Returns the charge template for a protein residue.
+    ///
+    /// See [`ProteinScheme::residue`].
+    pub fn residue(self, pos: impl ProteinPositionArg, residue: &str) -> Option<ResidueTemplate> {
+        self.resolve(pos, residue).map(ResidueHandle::template)
+    }
+
+    /// Resolves a protein residue to a handle for repeated lookups.
+    ///
+    /// See [`ProteinScheme::resolve`].
+    pub fn resolve(self, pos: impl ProteinPositionArg, residue: &str) -> Option<ResidueHandle> {
+        let mut buf = [0; MAX_NAME_LEN];
+        self.scheme.resolve(pos, normalize(residue, &mut buf)?)
+    }
+}
+
+impl Normalized<NucleicScheme> {
+    /// Returns the partial charge for an atom in a nucleic acid residue.
+    ///
+    /// See [`NucleicScheme::charge`].
+    pub fn charge(self, pos: impl NucleicPositionArg, residue: &str, atom: &str) -> Option<f32> {
+        let mut buf = [0; MAX_NAME_LEN];
+        self.resolve(pos, residue)?
+            .charge(normalize(atom, &mut buf)?)
+    }
+
+    /// Returns the charge template for a nucleic acid residue.
+    ///
+    /// See [`NucleicScheme::residue`].
+    pub fn residue(self, pos: impl NucleicPositionArg, residue: &str) -> Option<ResidueTemplate> {
+        self.resolve(pos, residue).map(ResidueHandle::template)
+    }
+
+    /// Resolves a nucleic acid residue to a handle for repeated lookups.
+    ///
+    /// See [`NucleicScheme::resolve`].
+    pub fn resolve(self, pos: impl NucleicPositionArg, residue: &str) -> Option<ResidueHandle> {
+        let mut buf = [0; MAX_NAME_LEN];
+        self.scheme.resolve(pos, normalize(residue, &mut buf)?)
+    }
+}
+
+impl Normalized<WaterScheme> {
+    /// Returns the partial charges for a water residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `residue` - Residue name (e.g., "HOH", "hoh").
+    ///
+    /// # Returns
+    ///
+    /// `Option<WaterCharges>` - Partial charges if the residue is water, otherwise `None`.
+    pub fn charges(self, residue: &str) -> Option<WaterCharges> {
+        let mut buf = [0; MAX_NAME_LEN];
+        match normalize(residue, &mut buf)? {
+            "HOH" => self.scheme.charges(),
+            _ => None,
+        }
+    }
+}
+
+impl Normalized<IonScheme> {
+    /// Returns the partial charge for an ion residue.
+    ///
+    /// See [`IonScheme::charge`].
+    pub fn charge(self, residue: &str) -> Option<f32> {
+        let mut buf = [0; MAX_NAME_LEN];
+        self.scheme.charge(normalize(residue, &mut buf)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_names() {
+        let mut buf = [0; MAX_NAME_LEN];
+        assert_eq!(normalize("ala", &mut buf), Some("ALA"));
+        assert_eq!(normalize(" Hid\t", &mut buf), Some("HID"));
+        assert_eq!(normalize("h5''", &mut buf), Some("H5''"));
+        assert_eq!(normalize("", &mut buf), Some(""));
+    }
+
+    #[test]
+    fn normalize_too_long() {
+        let mut buf = [0; MAX_NAME_LEN];
+        assert_eq!(normalize("ALANINE", &mut buf), None);
+    }
+
+    #[test]
+    fn normalize_non_ascii() {
+        let mut buf = [0; MAX_NAME_LEN];
+        assert_eq!(normalize("é", &mut buf), Some("é"));
+    }
+}
@@ -3,7 +3,7 @@
 /// Position of a residue in its chain.
 ///
 /// Terminal residues have different charge distributions than internal ones.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum Position {
     /// N-terminal (protein).
     NTerminal,
@@ -13,30 +13,234 @@ pub enum Position {
     CTerminal,
     /// C-terminal protonated (protein).
     CTerminalProtonated,
+    /// Both N- and C-terminal, zwitterionic free amino acid (protein).
+    ///
+    /// The templates combine the `n` and `c` templates of each scheme and are not
+    /// published parameters; they are only compiled in with the `derived-templates`
+    /// feature. See `data/derived.csv` for how they are built.
+    NCTerminal,
+    /// Both N- and C-terminal, neutral free amino acid (protein).
+    ///
+    /// Combines the `n-` and `c+` templates; derived like [`Position::NCTerminal`].
+    NCTerminalNeutral,
     /// 5'-terminal (nucleic acid).
     FivePrime,
     /// 3'-terminal (nucleic acid).
     ThreePrime,
+    /// Both 5'- and 3'-terminal, free nucleoside without phosphate (nucleic acid).
+    ///
+    /// Combines the `5` and `3` templates; derived like [`Position::NCTerminal`]. This is
+    /// a nucleoside, not a mononucleotide: none of the schemes publishes a 5'-phosphate
+    /// terminus.
+    FiveThreePrime,
     /// Internal residue.
     #[default]
     Middle,
 }
 
 impl Position {
+    /// Returns the index of this position in the generated tables.
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+
     /// Returns the key for this position.
+    #[cfg(any(test, feature = "std"))]
     pub(crate) const fn key(self) -> &'static str {
         match self {
             Self::NTerminal => "n",
             Self::NTerminalDeprotonated => "n-",
             Self::CTerminal => "c",
             Self::CTerminalProtonated => "c+",
+            Self::NCTerminal => "nc",
+            Self::NCTerminalNeutral => "n-c+",
             Self::FivePrime => "5",
             Self::ThreePrime => "3",
+            Self::FiveThreePrime => "53",
             Self::Middle => "m",
         }
     }
+
+    /// Returns the position for a key, as used in the data files.
+    #[cfg(feature = "std")]
+    pub(crate) fn from_key(key: &str) -> Option<Self> {
+        const ALL: [Position; 10] = [
+            Position::NTerminal,
+            Position::NTerminalDeprotonated,
+            Position::CTerminal,
+            Position::CTerminalProtonated,
+            Position::NCTerminal,
+            Position::NCTerminalNeutral,
+            Position::FivePrime,
+            Position::ThreePrime,
+            Position::FiveThreePrime,
+            Position::Middle,
+        ];
+        ALL.into_iter().find(|pos| pos.key() == key)
+    }
+}
+
+/// Position of a residue in a protein chain.
+///
+/// Unlike [`Position`], this type cannot express nucleic acid termini, so protein
+/// lookups made with it are checked at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ProteinPosition {
+    /// N-terminal (protonated NH₃⁺).
+    NTerminal,
+    /// N-terminal deprotonated (neutral NH₂).
+    NTerminalDeprotonated,
+    /// C-terminal (deprotonated COO⁻).
+    CTerminal,
+    /// C-terminal protonated (COOH).
+    CTerminalProtonated,
+    /// Free amino acid, zwitterionic (NH₃⁺ and COO⁻).
+    ///
+    /// Derived, see [`Position::NCTerminal`].
+    NCTerminal,
+    /// Free amino acid, neutral (NH₂ and COOH).
+    ///
+    /// Derived, see [`Position::NCTerminal`].
+    NCTerminalNeutral,
+    /// Internal residue.
+    #[default]
+    Middle,
+}
+
+impl ProteinPosition {
+    /// Returns all protein positions.
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::NTerminal,
+            Self::NTerminalDeprotonated,
+            Self::CTerminal,
+            Self::CTerminalProtonated,
+            Self::NCTerminal,
+            Self::NCTerminalNeutral,
+            Self::Middle,
+        ]
+    }
+}
+
+impl From<ProteinPosition> for Position {
+    fn from(pos: ProteinPosition) -> Self {
+        match pos {
+            ProteinPosition::NTerminal => Self::NTerminal,
+            ProteinPosition::NTerminalDeprotonated => Self::NTerminalDeprotonated,
+            ProteinPosition::CTerminal => Self::CTerminal,
+            ProteinPosition::CTerminalProtonated => Self::CTerminalProtonated,
+            ProteinPosition::NCTerminal => Self::NCTerminal,
+            ProteinPosition::NCTerminalNeutral => Self::NCTerminalNeutral,
+            ProteinPosition::Middle => Self::Middle,
+        }
+    }
+}
+
+impl TryFrom<Position> for ProteinPosition {
+    type Error = crate::ChargeError<'static>;
+
+    /// Converts a [`Position`], failing with
+    /// [`ChargeError::UnsupportedPosition`](crate::ChargeError::UnsupportedPosition) for
+    /// nucleic acid termini.
+    fn try_from(pos: Position) -> Result<Self, Self::Error> {
+        match pos {
+            Position::NTerminal => Ok(Self::NTerminal),
+            Position::NTerminalDeprotonated => Ok(Self::NTerminalDeprotonated),
+            Position::CTerminal => Ok(Self::CTerminal),
+            Position::CTerminalProtonated => Ok(Self::CTerminalProtonated),
+            Position::NCTerminal => Ok(Self::NCTerminal),
+            Position::NCTerminalNeutral => Ok(Self::NCTerminalNeutral),
+            Position::Middle => Ok(Self::Middle),
+            other => Err(crate::ChargeError::UnsupportedPosition(other)),
+        }
+    }
+}
+
+/// Position of a residue in a nucleic acid chain.
+///
+/// Unlike [`Position`], this type cannot express protein termini, so nucleic acid
+/// lookups made with it are checked at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NucleicPosition {
+    /// 5'-terminal.
+    FivePrime,
+    /// 3'-terminal.
+    ThreePrime,
+    /// Free nucleoside (5'-OH and 3'-OH, no phosphate), both 5'- and 3'-terminal.
+    ///
+    /// Derived, see [`Position::FiveThreePrime`].
+    FiveThreePrime,
+    /// Internal residue.
+    #[default]
+    Middle,
+}
+
+impl NucleicPosition {
+    /// Returns all nucleic acid positions.
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::FivePrime,
+            Self::ThreePrime,
+            Self::FiveThreePrime,
+            Self::Middle,
+        ]
+    }
+}
+
+impl From<NucleicPosition> for Position {
+    fn from(pos: NucleicPosition) -> Self {
+        match pos {
+            NucleicPosition::FivePrime => Self::FivePrime,
+            NucleicPosition::ThreePrime => Self::ThreePrime,
+            NucleicPosition::FiveThreePrime => Self::FiveThreePrime,
+            NucleicPosition::Middle => Self::Middle,
+        }
+    }
 }
 
+impl TryFrom<Position> for NucleicPosition {
+    type Error = crate::ChargeError<'static>;
+
+    /// Converts a [`Position`], failing with
+    /// [`ChargeError::UnsupportedPosition`](crate::ChargeError::UnsupportedPosition) for
+    /// protein termini.
+    fn try_from(pos: Position) -> Result<Self, Self::Error> {
+        match pos {
+            Position::FivePrime => Ok(Self::FivePrime),
+            Position::ThreePrime => Ok(Self::ThreePrime),
+            Position::FiveThreePrime => Ok(Self::FiveThreePrime),
+            Position::Middle => Ok(Self::Middle),
+            other => Err(crate::ChargeError::UnsupportedPosition(other)),
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Position {}
+    impl Sealed for super::ProteinPosition {}
+    impl Sealed for super::NucleicPosition {}
+}
+
+/// Position argument accepted by [`ProteinScheme`](crate::ProteinScheme) lookups.
+///
+/// Implemented for [`ProteinPosition`] (validated at compile time) and [`Position`]
+/// (validated at lookup time). This trait is sealed.
+pub trait ProteinPositionArg: Copy + Into<Position> + sealed::Sealed {}
+
+impl ProteinPositionArg for Position {}
+impl ProteinPositionArg for ProteinPosition {}
+
+/// Position argument accepted by [`NucleicScheme`](crate::NucleicScheme) lookups.
+///
+/// Implemented for [`NucleicPosition`] (validated at compile time) and [`Position`]
+/// (validated at lookup time). This trait is sealed.
+pub trait NucleicPositionArg: Copy + Into<Position> + sealed::Sealed {}
+
+impl NucleicPositionArg for Position {}
+impl NucleicPositionArg for NucleicPosition {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,13 +251,68 @@ mod tests {
         assert_eq!(Position::NTerminalDeprotonated.key(), "n-");
         assert_eq!(Position::CTerminal.key(), "c");
         assert_eq!(Position::CTerminalProtonated.key(), "c+");
+        assert_eq!(Position::NCTerminal.key(), "nc");
+        assert_eq!(Position::NCTerminalNeutral.key(), "n-c+");
         assert_eq!(Position::FivePrime.key(), "5");
         assert_eq!(Position::ThreePrime.key(), "3");
+        assert_eq!(Position::FiveThreePrime.key(), "53");
         assert_eq!(Position::Middle.key(), "m");
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_key() {
+        for key in crate::generated::POSITION_KEYS {
+            assert_eq!(Position::from_key(key).map(Position::key), Some(key));
+        }
+        assert_eq!(Position::from_key("x"), None);
+    }
+
+    #[test]
+    fn table_order() {
+        let positions = [
+            Position::NTerminal,
+            Position::NTerminalDeprotonated,
+            Position::CTerminal,
+            Position::CTerminalProtonated,
+            Position::NCTerminal,
+            Position::NCTerminalNeutral,
+            Position::FivePrime,
+            Position::ThreePrime,
+            Position::FiveThreePrime,
+            Position::Middle,
+        ];
+        assert_eq!(positions.len(), crate::generated::POSITION_KEYS.len());
+        for (i, pos) in positions.into_iter().enumerate() {
+            assert_eq!(pos.index(), i);
+            assert_eq!(crate::generated::POSITION_KEYS[i], pos.key());
+        }
+    }
+
     #[test]
     fn default_is_middle() {
         assert_eq!(Position::default(), Position::Middle);
+        assert_eq!(ProteinPosition::default(), ProteinPosition::Middle);
+        assert_eq!(NucleicPosition::default(), NucleicPosition::Middle);
+    }
+
+    #[test]
+    fn protein_round_trip() {
+        for &pos in ProteinPosition::all() {
+            assert_eq!(ProteinPosition::try_from(Position::from(pos)), Ok(pos));
+        }
+        assert!(ProteinPosition::try_from(Position::FivePrime).is_err());
+        assert!(ProteinPosition::try_from(Position::ThreePrime).is_err());
+        assert!(ProteinPosition::try_from(Position::FiveThreePrime).is_err());
+    }
+
+    #[test]
+    fn nucleic_round_trip() {
+        for &pos in NucleicPosition::all() {
+            assert_eq!(NucleicPosition::try_from(Position::from(pos)), Ok(pos));
+        }
+        assert!(NucleicPosition::try_from(Position::NTerminal).is_err());
+        assert!(NucleicPosition::try_from(Position::CTerminalProtonated).is_err());
+        assert!(NucleicPosition::try_from(Position::NCTerminal).is_err());
     }
 }
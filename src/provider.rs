@@ -0,0 +1,406 @@
+//! Common interface over charge sources.
+
+use crate::{
+    ExtraScheme, ForceField, IonScheme, NucleicScheme, Position, ProteinScheme, ResidueClass,
+    ResidueTemplate, WaterCharges, WaterScheme,
+};
+
+/// A source of residue partial charges.
+///
+/// Implemented by the built-in scheme enums and [`ForceField`], and usable as a trait
+/// object, so generic code can accept any charge source, including user-defined and
+/// mock providers. Providers are combined with
+/// [`with_fallback`](ResidueChargeProvider::with_fallback).
+pub trait ResidueChargeProvider {
+    /// Returns the partial charge for an atom in a residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "ALA", "DA").
+    /// * `atom` - Atom name (e.g., "CA", "P").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if found, otherwise `None`.
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32>;
+
+    /// Returns the atoms and partial charges of a residue, in force field order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "ALA", "DA").
+    ///
+    /// # Returns
+    ///
+    /// `Option<ResidueAtoms>` - Atom iterator if the residue is defined, otherwise `None`.
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>>;
+
+    /// Returns `true` if the provider defines the residue at the position.
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        self.residue_atoms(pos, residue).is_some()
+    }
+
+    /// Returns `true` if the provider has templates at the position.
+    ///
+    /// Providers that define residues at every position (e.g., water and ions) keep the
+    /// default, which returns `true`.
+    fn supports_position(&self, _pos: Position) -> bool {
+        true
+    }
+
+    /// Returns a provider that uses `fallback` for residues this provider does not define.
+    ///
+    /// Residues are never mixed: once a provider defines a residue, all of its atoms are
+    /// looked up in that provider.
+    fn with_fallback<P>(self, fallback: P) -> Fallback<Self, P>
+    where
+        Self: Sized,
+        P: ResidueChargeProvider,
+    {
+        Fallback {
+            primary: self,
+            fallback,
+        }
+    }
+}
+
+/// Iterator over the atom names and partial charges of a residue.
+#[derive(Debug, Clone)]
+pub struct ResidueAtoms<'a> {
+    inner: AtomsInner<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum AtomsInner<'a> {
+    Slice(core::slice::Iter<'a, (&'a str, f32)>),
+    Water(WaterCharges, usize),
+    Single(core::option::IntoIter<(&'a str, f32)>),
+    #[cfg(feature = "alloc")]
+    Owned(core::slice::Iter<'a, (alloc::string::String, f32)>),
+    #[cfg(feature = "compact")]
+    Packed(core::iter::Zip<core::slice::Iter<'a, u16>, core::slice::Iter<'a, i32>>),
+}
+
+impl<'a> From<&'a [(&'a str, f32)]> for ResidueAtoms<'a> {
+    fn from(atoms: &'a [(&'a str, f32)]) -> Self {
+        Self {
+            inner: AtomsInner::Slice(atoms.iter()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a [(alloc::string::String, f32)]> for ResidueAtoms<'a> {
+    fn from(atoms: &'a [(alloc::string::String, f32)]) -> Self {
+        Self {
+            inner: AtomsInner::Owned(atoms.iter()),
+        }
+    }
+}
+
+impl ResidueAtoms<'_> {
+    /// Creates an iterator over interned atom names and encoded charges.
+    #[cfg(feature = "compact")]
+    pub(crate) fn packed(names: &'static [u16], charges: &'static [i32]) -> Self {
+        Self {
+            inner: AtomsInner::Packed(names.iter().zip(charges)),
+        }
+    }
+
+    fn water(charges: WaterCharges) -> Self {
+        Self {
+            inner: AtomsInner::Water(charges, 0),
+        }
+    }
+}
+
+impl<'a> ResidueAtoms<'a> {
+    fn single(atom: &'a str, charge: f32) -> Self {
+        Self {
+            inner: AtomsInner::Single(Some((atom, charge)).into_iter()),
+        }
+    }
+}
+
+impl<'a> Iterator for ResidueAtoms<'a> {
+    type Item = (&'a str, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            AtomsInner::Slice(iter) => iter.next().copied(),
+            AtomsInner::Water(charges, i) => {
+                let atom = *["O", "H1", "H2"].get(*i)?;
+                *i += 1;
+                Some((atom, charges.charge(atom)?))
+            }
+            AtomsInner::Single(iter) => iter.next(),
+            #[cfg(feature = "alloc")]
+            AtomsInner::Owned(iter) => iter.next().map(|(a, c)| (a.as_str(), *c)),
+            #[cfg(feature = "compact")]
+            AtomsInner::Packed(iter) => iter.next().map(|(&a, &q)| {
+                (
+                    crate::generated::ATOM_NAMES[a as usize],
+                    crate::Charge::from_units(q.into()).to_f32(),
+                )
+            }),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match &self.inner {
+            AtomsInner::Slice(iter) => iter.len(),
+            AtomsInner::Water(_, i) => 3 - i,
+            AtomsInner::Single(iter) => iter.len(),
+            #[cfg(feature = "alloc")]
+            AtomsInner::Owned(iter) => iter.len(),
+            #[cfg(feature = "compact")]
+            AtomsInner::Packed(iter) => iter.len(),
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ResidueAtoms<'_> {}
+
+/// Two providers chained by residue.
+///
+/// Created with [`ResidueChargeProvider::with_fallback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fallback<A, B> {
+    primary: A,
+    fallback: B,
+}
+
+impl<A, B> Fallback<A, B> {
+    /// Returns the provider consulted first.
+    pub const fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns the provider consulted for residues the primary does not define.
+    pub const fn fallback(&self) -> &B {
+        &self.fallback
+    }
+}
+
+impl<A: ResidueChargeProvider, B: ResidueChargeProvider> ResidueChargeProvider for Fallback<A, B> {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        if self.primary.contains_residue(pos, residue) {
+            self.primary.charge(pos, residue, atom)
+        } else {
+            self.fallback.charge(pos, residue, atom)
+        }
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        self.primary
+            .residue_atoms(pos, residue)
+            .or_else(|| self.fallback.residue_atoms(pos, residue))
+    }
+
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        self.primary.contains_residue(pos, residue) || self.fallback.contains_residue(pos, residue)
+    }
+
+    fn supports_position(&self, pos: Position) -> bool {
+        self.primary.supports_position(pos) || self.fallback.supports_position(pos)
+    }
+}
+
+impl<P: ResidueChargeProvider + ?Sized> ResidueChargeProvider for &P {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        (**self).charge(pos, residue, atom)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        (**self).residue_atoms(pos, residue)
+    }
+
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        (**self).contains_residue(pos, residue)
+    }
+
+    fn supports_position(&self, pos: Position) -> bool {
+        (**self).supports_position(pos)
+    }
+}
+
+impl ResidueChargeProvider for ExtraScheme {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        ExtraScheme::charge(*self, pos, residue, atom)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        self.residue(pos, residue).map(ResidueTemplate::into_iter)
+    }
+
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        self.resolve(pos, residue).is_some()
+    }
+
+    fn supports_position(&self, pos: Position) -> bool {
+        self.positions().contains(&pos)
+    }
+}
+
+impl ResidueChargeProvider for ProteinScheme {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        ProteinScheme::charge(*self, pos, residue, atom)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        self.residue(pos, residue).map(ResidueTemplate::into_iter)
+    }
+
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        self.resolve(pos, residue).is_some()
+    }
+
+    fn supports_position(&self, pos: Position) -> bool {
+        self.positions().contains(&pos)
+    }
+}
+
+impl ResidueChargeProvider for NucleicScheme {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        NucleicScheme::charge(*self, pos, residue, atom)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        self.residue(pos, residue).map(ResidueTemplate::into_iter)
+    }
+
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        self.resolve(pos, residue).is_some()
+    }
+
+    fn supports_position(&self, pos: Position) -> bool {
+        self.positions().contains(&pos)
+    }
+}
+
+/// Defines `HOH` at every position, with atoms `O`, `H1` and `H2`.
+impl ResidueChargeProvider for WaterScheme {
+    fn charge(&self, _pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        match residue {
+            "HOH" => self.charges()?.charge(atom),
+            _ => None,
+        }
+    }
+
+    fn residue_atoms(&self, _pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        match residue {
+            "HOH" => self.charges().map(ResidueAtoms::water),
+            _ => None,
+        }
+    }
+}
+
+/// Defines each ion at every position, as a single atom. The atom name can differ from the
+/// residue name (e.g., "I" in "IOD"), and [`ResidueChargeProvider::charge`] returns `None`
+/// for any other atom name.
+impl ResidueChargeProvider for IonScheme {
+    fn charge(&self, _pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        self.atom_charge(residue, atom)
+    }
+
+    fn residue_atoms(&self, _pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        let (atom, charge) = self.ion(residue)?;
+        Some(ResidueAtoms::single(atom, charge))
+    }
+}
+
+impl ResidueChargeProvider for ForceField {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        ForceField::charge(*self, pos, residue, atom)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        match crate::classify(residue)? {
+            ResidueClass::Protein => self.protein.residue_atoms(pos, residue),
+            ResidueClass::Dna | ResidueClass::Rna => self.nucleic.residue_atoms(pos, residue),
+            ResidueClass::Water => self.water.residue_atoms(pos, residue),
+            ResidueClass::Ion => self.ion.residue_atoms(pos, residue),
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "amber-ffsb", feature = "water-tip3p", feature = "ions")
+))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "amber-ffsb")]
+    /// Provider defining a single two-atom residue `LIG` at every position.
+    struct Mock;
+
+    #[cfg(feature = "amber-ffsb")]
+    const LIG: &[(&str, f32)] = &[("C1", 0.25), ("O1", -0.25)];
+
+    #[cfg(feature = "amber-ffsb")]
+    impl ResidueChargeProvider for Mock {
+        fn charge(&self, _pos: Position, residue: &str, atom: &str) -> Option<f32> {
+            match residue {
+                "LIG" => LIG.iter().find(|(a, _)| *a == atom).map(|(_, c)| *c),
+                _ => None,
+            }
+        }
+
+        fn residue_atoms(&self, _pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+            match residue {
+                "LIG" => Some(LIG.into()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "amber-ffsb")]
+    fn fallback_by_residue() {
+        let p = Mock.with_fallback(ProteinScheme::AmberFFSB);
+        assert_eq!(p.charge(Position::Middle, "LIG", "C1"), Some(0.25));
+        assert_eq!(
+            p.charge(Position::Middle, "ALA", "CA"),
+            ProteinScheme::AmberFFSB.charge(Position::Middle, "ALA", "CA")
+        );
+        assert_eq!(p.charge(Position::Middle, "LIG", "CA"), None);
+        assert!(p.contains_residue(Position::Middle, "ALA"));
+        assert!(!p.contains_residue(Position::Middle, "XYZ"));
+    }
+
+    #[test]
+    #[cfg(feature = "water-tip3p")]
+    fn water_atoms() {
+        let w = WaterScheme::Tip3p.charges().unwrap();
+        let atoms = WaterScheme::Tip3p
+            .residue_atoms(Position::Middle, "HOH")
+            .unwrap();
+        assert_eq!(atoms.len(), 3);
+        assert!(atoms.eq([("O", w.o), ("H1", w.h1), ("H2", w.h2)]));
+    }
+
+    #[test]
+    #[cfg(feature = "ions")]
+    fn ion_atoms() {
+        let atoms = IonScheme::Classic
+            .residue_atoms(Position::Middle, "MG")
+            .unwrap();
+        assert!(atoms.eq([("MG", 2.0)]));
+        let atoms = IonScheme::Classic
+            .residue_atoms(Position::Middle, "IOD")
+            .unwrap();
+        assert!(atoms.eq([("I", -1.0)]));
+        let ion: &dyn ResidueChargeProvider = &IonScheme::Classic;
+        assert_eq!(ion.charge(Position::Middle, "IOD", "I"), Some(-1.0));
+        assert_eq!(ion.charge(Position::Middle, "NA", "XX"), None);
+        assert!(
+            IonScheme::Classic
+                .residue_atoms(Position::Middle, "XX")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Runtime residue template registry.
+
+use alloc::string::String;
+
+use crate::{ChargeError, CustomScheme, Position, ResidueAtoms, ResidueChargeProvider};
+
+/// User-supplied residue templates overlaid on a base charge provider.
+///
+/// Templates are keyed by position and residue name. A residue defined in the registry
+/// replaces the base residue at that position entirely; all other lookups go to the
+/// base provider (typically a [`ProteinScheme`](crate::ProteinScheme) or
+/// [`NucleicScheme`](crate::NucleicScheme)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChargeRegistry<B> {
+    base: B,
+    overlay: CustomScheme,
+}
+
+impl<B: ResidueChargeProvider> ChargeRegistry<B> {
+    /// Creates an empty registry on top of a base provider.
+    pub const fn new(base: B) -> Self {
+        Self {
+            base,
+            overlay: CustomScheme::new(),
+        }
+    }
+
+    /// Returns the base provider.
+    pub const fn base(&self) -> &B {
+        &self.base
+    }
+
+    /// Returns the user residue templates.
+    pub const fn overlay(&self) -> &CustomScheme {
+        &self.overlay
+    }
+
+    /// Adds or replaces a residue template.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    /// * `atoms` - Atom names and partial charges, in force field order.
+    ///
+    /// # Returns
+    ///
+    /// `bool` - `true` if a user template for the residue and position was replaced.
+    pub fn insert<R, A, I>(&mut self, pos: Position, residue: R, atoms: I) -> bool
+    where
+        R: Into<String>,
+        A: Into<String>,
+        I: IntoIterator<Item = (A, f32)>,
+    {
+        self.overlay.insert(pos, residue, atoms)
+    }
+
+    /// Adds or replaces a residue template, returning the registry.
+    ///
+    /// See [`ChargeRegistry::insert`].
+    pub fn with_residue<R, A, I>(mut self, pos: Position, residue: R, atoms: I) -> Self
+    where
+        R: Into<String>,
+        A: Into<String>,
+        I: IntoIterator<Item = (A, f32)>,
+    {
+        self.insert(pos, residue, atoms);
+        self
+    }
+
+    /// Removes a user residue template, restoring the base residue.
+    ///
+    /// # Returns
+    ///
+    /// `bool` - `true` if a user template was removed.
+    pub fn remove(&mut self, pos: Position, residue: &str) -> bool {
+        self.overlay.remove(pos, residue)
+    }
+
+    /// Returns the number of user residue templates.
+    pub fn len(&self) -> usize {
+        self.overlay.len()
+    }
+
+    /// Returns `true` if the registry has no user residue templates.
+    pub fn is_empty(&self) -> bool {
+        self.overlay.is_empty()
+    }
+
+    /// Returns the partial charge for an atom, preferring user templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    /// * `atom` - Atom name (e.g., "C1", "CA").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if found, otherwise `None`.
+    pub fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        if self.overlay.contains_residue(pos, residue) {
+            self.overlay.charge(pos, residue, atom)
+        } else {
+            self.base.charge(pos, residue, atom)
+        }
+    }
+
+    /// Returns the atoms and partial charges of a residue, preferring user templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    ///
+    /// # Returns
+    ///
+    /// `Option<ResidueAtoms>` - Atom iterator if the residue is defined, otherwise `None`.
+    pub fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        self.overlay
+            .residue_atoms(pos, residue)
+            .or_else(|| self.base.residue_atoms(pos, residue))
+    }
+
+    /// Returns the partial charge for an atom, preferring user templates and reporting
+    /// why the lookup failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    /// * `atom` - Atom name (e.g., "C1", "CA").
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChargeError::UnsupportedPosition`] if the residue is not in the registry
+    /// and the base provider has no templates at the position,
+    /// [`ChargeError::UnknownResidue`] if neither the registry nor the base provider
+    /// defines the residue, or [`ChargeError::UnknownAtom`] if the template that defines
+    /// it lacks the atom.
+    pub fn try_charge<'a>(
+        &self,
+        pos: Position,
+        residue: &'a str,
+        atom: &'a str,
+    ) -> Result<f32, ChargeError<'a>> {
+        match self.charge(pos, residue, atom) {
+            Some(charge) => Ok(charge),
+            None => {
+                self.try_residue(pos, residue)?;
+                Err(ChargeError::UnknownAtom { residue, atom })
+            }
+        }
+    }
+
+    /// Returns the atoms and partial charges of a residue, preferring user templates and
+    /// reporting why the lookup failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG", "ALA").
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChargeError::UnsupportedPosition`] if the residue is not in the registry
+    /// and the base provider has no templates at the position, or
+    /// [`ChargeError::UnknownResidue`] if neither the registry nor the base provider
+    /// defines the residue.
+    pub fn try_residue<'a>(
+        &self,
+        pos: Position,
+        residue: &'a str,
+    ) -> Result<ResidueAtoms<'_>, ChargeError<'a>> {
+        self.residue_atoms(pos, residue).ok_or_else(|| {
+            if self.base.supports_position(pos) {
+                ChargeError::UnknownResidue(residue)
+            } else {
+                ChargeError::UnsupportedPosition(pos)
+            }
+        })
+    }
+
+    /// Returns `true` if the registry or the base provider defines the residue.
+    pub fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        self.overlay.contains_residue(pos, residue) || self.base.contains_residue(pos, residue)
+    }
+}
+
+impl<B: ResidueChargeProvider> ResidueChargeProvider for ChargeRegistry<B> {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        ChargeRegistry::charge(self, pos, residue, atom)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        ChargeRegistry::residue_atoms(self, pos, residue)
+    }
+
+    fn contains_residue(&self, pos: Position, residue: &str) -> bool {
+        ChargeRegistry::contains_residue(self, pos, residue)
+    }
+
+    fn supports_position(&self, pos: Position) -> bool {
+        self.base.supports_position(pos)
+    }
+}
+
+#[cfg(all(test, any(feature = "amber-ffsb", feature = "charmm")))]
+mod tests {
+    use super::*;
+    use crate::ProteinScheme;
+
+    #[test]
+    #[cfg(feature = "amber-ffsb")]
+    fn override_and_restore() {
+        let base = ProteinScheme::AmberFFSB;
+        let mut r = ChargeRegistry::new(base);
+        assert!(!r.insert(Position::Middle, "ALA", [("N", -0.5), ("CA", 0.5)]));
+        assert_eq!(r.charge(Position::Middle, "ALA", "CA"), Some(0.5));
+        assert_eq!(
+            r.residue_atoms(Position::Middle, "ALA").map(|a| a.len()),
+            Some(2)
+        );
+        assert_eq!(
+            r.try_charge(Position::Middle, "ALA", "CB"),
+            Err(ChargeError::UnknownAtom {
+                residue: "ALA",
+                atom: "CB"
+            })
+        );
+
+        assert!(r.remove(Position::Middle, "ALA"));
+        assert!(r.is_empty());
+        assert_eq!(
+            r.charge(Position::Middle, "ALA", "CA"),
+            base.charge(Position::Middle, "ALA", "CA")
+        );
+        assert_eq!(
+            r.try_charge(Position::Middle, "ALA", "CB"),
+            base.try_charge(Position::Middle, "ALA", "CB")
+        );
+        let atoms = r.residue_atoms(Position::Middle, "ALA").unwrap();
+        let template = base.residue(Position::Middle, "ALA").unwrap();
+        assert_eq!(atoms.len(), template.len());
+        assert!(atoms.zip(template).all(|(a, b)| a == b));
+        assert_eq!(
+            r.try_charge(Position::Middle, "LIG", "C1"),
+            Err(ChargeError::UnknownResidue("LIG"))
+        );
+        assert!(r.try_residue(Position::Middle, "LIG").is_err());
+        assert_eq!(
+            r.try_residue(Position::FivePrime, "ALA").err(),
+            Some(ChargeError::UnsupportedPosition(Position::FivePrime))
+        );
+        assert_eq!(
+            r.try_charge(Position::FivePrime, "ALA", "CA"),
+            base.try_charge(Position::FivePrime, "ALA", "CA")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "charmm")]
+    fn user_templates_take_precedence() {
+        let r = ChargeRegistry::new(ProteinScheme::Charmm).with_residue(
+            Position::Middle,
+            "GLY",
+            [("N", -0.3), ("CA", 0.3)],
+        );
+        assert_eq!(r.charge(Position::Middle, "GLY", "CA"), Some(0.3));
+        assert_eq!(r.charge(Position::Middle, "GLY", "HA2"), None);
+        assert_eq!(
+            r.charge(Position::NTerminal, "GLY", "HA2"),
+            ProteinScheme::Charmm.charge(Position::NTerminal, "GLY", "HA2")
+        );
+        assert_eq!(
+            r.residue_atoms(Position::Middle, "GLY").map(|a| a.len()),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Residue charge templates.
+
+use crate::{Charge, ResidueAtoms};
+
+/// Ordered atoms and partial charges of a residue at a given chain position.
+///
+/// Templates are obtained from [`ProteinScheme::residue`](crate::ProteinScheme::residue)
+/// and [`NucleicScheme::residue`](crate::NucleicScheme::residue), and borrow directly
+/// from the compiled charge tables.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResidueTemplate {
+    atoms: Atoms,
+}
+
+/// Storage of the atoms of a template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Atoms {
+    /// Atom names and partial charges, with the exact charges in units of 1e-7 e.
+    ///
+    /// The charges are stored twice: the `f32` pairs back the `const` slice returned by
+    /// [`ResidueTemplate::atoms`], and `f32` cannot hold every seven-decimal charge of
+    /// magnitude 1 or more (e.g., -1.1074 in ARN), so exact sums need the integers. The
+    /// integers are shared by templates with the same charges and add under a tenth to
+    /// the default tables; the `compact` layout stores only the integers.
+    #[cfg(any(test, not(feature = "compact")))]
+    Pairs(&'static [(&'static str, f32)], &'static [i32]),
+    /// Interned atom names and exact partial charges of the compact layout.
+    #[cfg(feature = "compact")]
+    Packed(&'static [u16], &'static [i32]),
+}
+
+impl ResidueTemplate {
+    /// Creates a template from a generated atom table and its exact charges.
+    #[cfg(any(test, not(feature = "compact")))]
+    pub(crate) const fn new(atoms: &'static [(&'static str, f32)], units: &'static [i32]) -> Self {
+        Self {
+            atoms: Atoms::Pairs(atoms, units),
+        }
+    }
+
+    /// Creates a template from interned atom names and exact charges of equal length.
+    #[cfg(feature = "compact")]
+    pub(crate) const fn packed(names: &'static [u16], charges: &'static [i32]) -> Self {
+        Self {
+            atoms: Atoms::Packed(names, charges),
+        }
+    }
+
+    /// Returns the atoms and their partial charges, in force field order.
+    ///
+    /// The `compact` feature does not store the charges as `f32`, so there this returns an
+    /// iterator over the same values instead; [`ResidueTemplate::iter`] works in both
+    /// layouts.
+    #[cfg(not(feature = "compact"))]
+    #[cfg_attr(docsrs, doc(cfg(not(feature = "compact"))))]
+    pub const fn atoms(self) -> &'static [(&'static str, f32)] {
+        let Atoms::Pairs(atoms, _) = self.atoms;
+        atoms
+    }
+
+    /// Returns the atoms and their partial charges, in force field order.
+    ///
+    /// Without the `compact` feature this returns a `&'static [(&'static str, f32)]`
+    /// slice instead; [`ResidueTemplate::iter`] works in both layouts.
+    #[cfg(feature = "compact")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compact")))]
+    pub fn atoms(self) -> ResidueAtoms<'static> {
+        self.into_iter()
+    }
+
+    /// Returns the number of atoms in the residue.
+    pub const fn len(self) -> usize {
+        match self.atoms {
+            #[cfg(any(test, not(feature = "compact")))]
+            Atoms::Pairs(atoms, _) => atoms.len(),
+            #[cfg(feature = "compact")]
+            Atoms::Packed(names, _) => names.len(),
+        }
+    }
+
+    /// Returns `true` if the residue has no atoms.
+    pub const fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over atom names and partial charges.
+    pub fn iter(self) -> impl ExactSizeIterator<Item = (&'static str, f32)> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over atom names.
+    pub fn atom_names(self) -> impl ExactSizeIterator<Item = &'static str> {
+        self.iter().map(|(a, _)| a)
+    }
+
+    /// Returns the partial charge of an atom in the residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name (e.g., "CA", "HB1").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if the atom exists, otherwise `None`.
+    pub fn charge(self, atom: &str) -> Option<f32> {
+        let index = self.position(atom)?;
+        match self.atoms {
+            #[cfg(any(test, not(feature = "compact")))]
+            Atoms::Pairs(atoms, _) => Some(atoms[index].1),
+            #[cfg(feature = "compact")]
+            Atoms::Packed(_, units) => Some(Charge::from_units(units[index].into()).to_f32()),
+        }
+    }
+
+    /// Returns `true` if the residue contains the given atom.
+    pub fn contains(self, atom: &str) -> bool {
+        self.position(atom).is_some()
+    }
+
+    /// Returns the index of an atom in force field order.
+    fn position(self, atom: &str) -> Option<usize> {
+        self.atom_names().position(|a| a == atom)
+    }
+
+    /// Returns the net charge of the residue (sum of all partial charges).
+    pub fn net_charge(self) -> f32 {
+        self.iter().map(|(_, c)| c).sum()
+    }
+
+    /// Returns the exact charges in units of 1e-7 e, in atom order.
+    const fn units(self) -> &'static [i32] {
+        match self.atoms {
+            #[cfg(any(test, not(feature = "compact")))]
+            Atoms::Pairs(_, units) => units,
+            #[cfg(feature = "compact")]
+            Atoms::Packed(_, units) => units,
+        }
+    }
+
+    /// Returns the exact partial charge of an atom in the residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom name (e.g., "CA", "HB1").
+    ///
+    /// # Returns
+    ///
+    /// `Option<Charge>` - Exact partial charge if the atom exists, otherwise `None`.
+    pub fn exact_charge(self, atom: &str) -> Option<Charge> {
+        let index = self.position(atom)?;
+        Some(Charge::from_units(self.units()[index].into()))
+    }
+
+    /// Returns an iterator over atom names and exact partial charges.
+    pub fn exact_charges(self) -> impl ExactSizeIterator<Item = (&'static str, Charge)> {
+        self.atom_names()
+            .zip(self.units())
+            .map(|(a, &q)| (a, Charge::from_units(q.into())))
+    }
+
+    /// Returns the exact net charge of the residue.
+    ///
+    /// Unlike [`ResidueTemplate::net_charge`], the sum carries no rounding error, so a
+    /// neutral or charged residue yields an exactly integral value. Use
+    /// [`NetCharge::of`](crate::NetCharge::of) to split the net charge of residues whose
+    /// published charges are not integral.
+    pub fn exact_net_charge(self) -> Charge {
+        self.exact_charges().map(|(_, q)| q).sum()
+    }
+}
+
+impl IntoIterator for ResidueTemplate {
+    type Item = (&'static str, f32);
+    type IntoIter = ResidueAtoms<'static>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self.atoms {
+            #[cfg(any(test, not(feature = "compact")))]
+            Atoms::Pairs(atoms, _) => atoms.into(),
+            #[cfg(feature = "compact")]
+            Atoms::Packed(names, charges) => ResidueAtoms::packed(names, charges),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ATOMS: &[(&str, f32)] = &[("N", -0.5), ("H", 0.25), ("C", 0.25)];
+    const UNITS: &[i32] = &[-5_000_000, 2_500_000, 2_500_000];
+
+    #[test]
+    fn accessors() {
+        let t = ResidueTemplate::new(ATOMS, UNITS);
+        assert_eq!(t.len(), 3);
+        assert!(!t.is_empty());
+        #[cfg(not(feature = "compact"))]
+        assert_eq!(t.atoms(), ATOMS);
+        #[cfg(feature = "compact")]
+        assert!(t.atoms().eq(ATOMS.iter().copied()));
+        assert!(t.atom_names().eq(["N", "H", "C"]));
+    }
+
+    #[test]
+    fn charge() {
+        let t = ResidueTemplate::new(ATOMS, UNITS);
+        assert_eq!(t.charge("H"), Some(0.25));
+        assert_eq!(t.charge("CA"), None);
+        assert!(t.contains("N"));
+        assert!(!t.contains("O"));
+    }
+
+    #[test]
+    fn net_charge() {
+        assert_eq!(ResidueTemplate::new(ATOMS, UNITS).net_charge(), 0.0);
+        assert_eq!(ResidueTemplate::new(&[], &[]).net_charge(), 0.0);
+    }
+
+    #[test]
+    fn exact_charge() {
+        let t = ResidueTemplate::new(ATOMS, UNITS);
+        assert_eq!(t.exact_charge("H"), Some(Charge::from_units(2_500_000)));
+        assert_eq!(t.exact_charge("CA"), None);
+        assert_eq!(t.exact_net_charge(), Charge::ZERO);
+        assert!(t.exact_charges().map(|(a, q)| (a, q.to_f32())).eq(t.iter()));
+        assert_eq!(
+            ResidueTemplate::new(&[], &[]).exact_net_charge(),
+            Charge::ZERO
+        );
+    }
+
+    #[test]
+    fn net_charge_of_chain() {
+        const CHARGED: &[(&str, f32)] = &[("N", 0.5), ("C", 0.4999847)];
+        let charged = ResidueTemplate::new(CHARGED, &[5_000_000, 4_999_847]);
+        let t = ResidueTemplate::new(ATOMS, UNITS);
+        let net = crate::NetCharge::of([t, charged, charged]);
+        assert_eq!(net.formal, 2);
+        assert_eq!(net.residual, Charge::from_units(-306));
+        assert!(crate::NetCharge::of([t, t]).is_integral());
+    }
+
+    #[test]
+    fn iteration_order() {
+        let t = ResidueTemplate::new(ATOMS, UNITS);
+        assert!(t.into_iter().eq(ATOMS.iter().copied()));
+        assert!(t.iter().eq(ATOMS.iter().copied()));
+    }
+}
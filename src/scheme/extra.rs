@@ -0,0 +1,175 @@
+//! Charge schemes added at build time.
+
+use core::fmt;
+
+/// Charge scheme added at build time.
+///
+/// Scheme keys that are not built in, found in the CSV files listed by the
+/// `FFCHARGE_EXTRA_DATA` environment variable, are compiled into the same perfect-hash
+/// tables as the bundled data. Each key becomes an associated constant named after it in
+/// upper case, with `-` replaced by `_` (e.g., "gaff2-lig" → `ExtraScheme::GAFF2_LIG`).
+/// Templates with an empty position apply at every position.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExtraScheme(u8);
+
+impl ExtraScheme {
+    /// Returns the scheme at an index of the generated tables.
+    pub(crate) const fn from_index(index: u8) -> Self {
+        Self(index)
+    }
+
+    /// Returns the index of this scheme in the generated tables.
+    const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Returns the key of this scheme, as used in the CSV files (e.g., "gaff2-lig").
+    pub fn key(self) -> &'static str {
+        crate::generated::EXTRA_SCHEME_KEYS[self.index()]
+    }
+
+    /// Returns the scheme with the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Scheme key, as used in the CSV files (e.g., "gaff2-lig").
+    ///
+    /// # Returns
+    ///
+    /// `Option<ExtraScheme>` - Scheme if it was added at build time, otherwise `None`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        crate::generated::EXTRA_SCHEME_KEYS
+            .iter()
+            .position(|&k| k == key)
+            .map(|i| Self::from_index(i as u8))
+    }
+
+    /// Returns all schemes added at build time, in sorted key order.
+    pub fn all() -> &'static [Self] {
+        &crate::generated::EXTRA_SCHEMES
+    }
+
+    /// Returns the positions for which this scheme has residue data.
+    ///
+    /// Positions are listed in declaration order of [`crate::Position`].
+    pub fn positions(self) -> &'static [crate::Position] {
+        crate::generated::extra_positions(self.index())
+    }
+
+    /// Returns the names of all residues available at a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    ///
+    /// # Returns
+    ///
+    /// `&'static [&'static str]` - Residue names in sorted order (empty if the position
+    /// is not supported by this scheme).
+    pub fn residues(self, pos: crate::Position) -> &'static [&'static str] {
+        crate::generated::extra_residues(self.index(), pos.index())
+    }
+
+    /// Resolves a residue to a handle for repeated lookups.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG").
+    ///
+    /// # Returns
+    ///
+    /// `Option<crate::ResidueHandle>` - Handle to the residue template if found,
+    /// otherwise `None`.
+    pub fn resolve(self, pos: crate::Position, residue: &str) -> Option<crate::ResidueHandle> {
+        crate::generated::get_extra_id(self.index(), pos.index(), residue)
+            .map(crate::ResidueHandle::new)
+    }
+
+    /// Returns the partial charge for an atom in a residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG").
+    /// * `atom` - Atom name (e.g., "C1").
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if found, otherwise `None`.
+    pub fn charge(self, pos: crate::Position, residue: &str, atom: &str) -> Option<f32> {
+        self.resolve(pos, residue)?.charge(atom)
+    }
+
+    /// Returns the exact partial charge for an atom in a residue.
+    ///
+    /// Exact charges sum without rounding error; see [`crate::Charge`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG").
+    /// * `atom` - Atom name (e.g., "C1").
+    ///
+    /// # Returns
+    ///
+    /// `Option<crate::Charge>` - Exact partial charge if found, otherwise `None`.
+    pub fn exact_charge(
+        self,
+        pos: crate::Position,
+        residue: &str,
+        atom: &str,
+    ) -> Option<crate::Charge> {
+        self.resolve(pos, residue)?.exact_charge(atom)
+    }
+
+    /// Returns the charge template for a residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "LIG").
+    ///
+    /// # Returns
+    ///
+    /// `Option<crate::ResidueTemplate>` - Ordered atoms and charges if found, otherwise `None`.
+    pub fn residue(self, pos: crate::Position, residue: &str) -> Option<crate::ResidueTemplate> {
+        self.resolve(pos, residue)
+            .map(crate::ResidueHandle::template)
+    }
+
+    /// Assigns partial charges to a batch of atoms.
+    ///
+    /// See [`crate::ProteinScheme::assign_into`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `atoms` and `out` have different lengths.
+    pub fn assign_into(self, atoms: &[crate::AtomKey<'_>], out: &mut [f32]) -> crate::AssignReport {
+        crate::assign::assign_with(atoms, out, |pos, res| self.resolve(pos, res))
+    }
+}
+
+impl fmt::Debug for ExtraScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtraScheme").field(&self.key()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_order() {
+        for (i, s) in ExtraScheme::all().iter().enumerate() {
+            assert_eq!(s.index(), i);
+            assert_eq!(ExtraScheme::from_key(s.key()), Some(*s));
+        }
+        assert_eq!(
+            ExtraScheme::all().len(),
+            crate::generated::EXTRA_SCHEME_KEYS.len()
+        );
+        assert_eq!(ExtraScheme::from_key("amber-ffsb"), None);
+    }
+}
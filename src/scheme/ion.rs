@@ -1,24 +1,49 @@
 //! Ion charge schemes.
 
 /// Ion charge scheme.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+///
+/// Compiled in by the `ions` cargo feature, which is enabled by default.
+/// See [scheme features](crate#scheme-features) before matching on the variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "ions", derive(Default))]
 pub enum IonScheme {
-    /// Classic (formal charges).
-    #[default]
+    /// Classic (formal charges). Feature `ions`.
+    #[cfg(feature = "ions")]
+    #[cfg_attr(feature = "ions", default)]
     Classic,
 }
 
 impl IonScheme {
+    /// Returns the index of this scheme in the generated tables.
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+
     /// Returns the internal key for this scheme.
+    #[cfg(test)]
     pub(crate) const fn key(self) -> &'static str {
         match self {
+            #[cfg(feature = "ions")]
             Self::Classic => "classic",
         }
     }
 
-    /// Returns all available schemes.
+    /// Returns all schemes compiled into this build.
     pub const fn all() -> &'static [Self] {
-        &[Self::Classic]
+        &[
+            #[cfg(feature = "ions")]
+            Self::Classic,
+        ]
+    }
+
+    /// Returns the names of all ion residues in this scheme.
+    ///
+    /// # Returns
+    ///
+    /// `&'static [&'static str]` - Residue names in sorted order.
+    pub fn residues(self) -> &'static [&'static str] {
+        crate::generated::ion_residues(self.index())
     }
 
     /// Returns the partial charge for an ion residue.
@@ -31,7 +56,65 @@ impl IonScheme {
     ///
     /// `Option<f32>` - Partial charge if found, otherwise `None`.
     pub fn charge(self, residue: &str) -> Option<f32> {
-        crate::generated::get_ion_charge(self.key(), residue)
+        crate::generated::get_ion(self.index(), residue).map(|(_, charge)| charge)
+    }
+
+    /// Returns the atom name and partial charge of an ion residue.
+    ///
+    /// The atom name can differ from the residue name (e.g., "I" in "IOD").
+    pub(crate) fn ion(self, residue: &str) -> Option<(&'static str, f32)> {
+        crate::generated::get_ion(self.index(), residue)
+    }
+
+    /// Returns the partial charge of an ion residue if `atom` is its atom.
+    pub(crate) fn atom_charge(self, residue: &str, atom: &str) -> Option<f32> {
+        let (name, charge) = self.ion(residue)?;
+        (name == atom).then_some(charge)
+    }
+
+    /// Returns the partial charge for an ion residue, from a raw byte column.
+    ///
+    /// # Arguments
+    ///
+    /// * `residue` - Residue name column (e.g., `b" NA"`); surrounding ASCII whitespace
+    ///   is ignored.
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if found, otherwise `None` (also for names that
+    /// are not valid UTF-8).
+    pub fn charge_bytes(self, residue: &[u8]) -> Option<f32> {
+        self.charge(crate::field::name(residue)?)
+    }
+
+    /// Returns the partial charge for an ion residue, reporting why the lookup failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `residue` - Residue name (e.g., "NA", "CL").
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::ChargeError::UnknownResidue`] if the ion is not defined by this
+    /// scheme.
+    pub fn try_charge(self, residue: &str) -> Result<f32, crate::ChargeError<'_>> {
+        self.charge(residue)
+            .ok_or(crate::ChargeError::UnknownResidue(residue))
+    }
+
+    /// Returns a lookup that accepts residue names in the given naming convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `convention` - Naming convention of the input residues.
+    pub const fn with_convention(self, convention: crate::Convention) -> crate::Aliased<Self> {
+        crate::Aliased::new(self, convention)
+    }
+
+    /// Returns a lookup that accepts names in any letter case, with surrounding
+    /// whitespace.
+    pub const fn normalized(self) -> crate::Normalized<Self> {
+        crate::Normalized::new(self)
     }
 }
 
@@ -40,11 +123,13 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "ions")]
     fn default() {
         assert_eq!(IonScheme::default(), IonScheme::Classic);
     }
 
     #[test]
+    #[cfg(feature = "ions")]
     fn count() {
         assert_eq!(IonScheme::all().len(), 1);
     }
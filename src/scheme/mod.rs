@@ -1,10 +1,12 @@
 //! Charge scheme definitions.
 
+mod extra;
 mod ion;
 mod nucleic;
 mod protein;
 mod water;
 
+pub use extra::ExtraScheme;
 pub use ion::IonScheme;
 pub use nucleic::NucleicScheme;
 pub use protein::ProteinScheme;
@@ -1,27 +1,89 @@
 //! Nucleic acid (DNA/RNA) charge schemes.
 
 /// Nucleic acid charge scheme.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+///
+/// Each variant is compiled in by the cargo feature named in its description; all are
+/// enabled by default.
+/// See [scheme features](crate#scheme-features) before matching on the variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "amber-nucleic", derive(Default))]
 pub enum NucleicScheme {
-    /// AMBER (DNA: OL15, OL21, OL24, bsc1; RNA: OL3).
-    #[default]
+    /// AMBER (DNA: OL15, OL21, OL24, bsc1; RNA: OL3). Feature `amber-nucleic`.
+    #[cfg(feature = "amber-nucleic")]
+    #[cfg_attr(feature = "amber-nucleic", default)]
     Amber,
-    /// CHARMM (C27, C36).
+    /// CHARMM (C27, C36). Feature `charmm`.
+    #[cfg(feature = "charmm")]
     Charmm,
 }
 
 impl NucleicScheme {
+    /// Returns the index of this scheme in the generated tables.
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+
     /// Returns the internal key for this scheme.
+    #[cfg(test)]
     pub(crate) const fn key(self) -> &'static str {
         match self {
+            #[cfg(feature = "amber-nucleic")]
             Self::Amber => "amber",
+            #[cfg(feature = "charmm")]
             Self::Charmm => "charmm",
         }
     }
 
-    /// Returns all available schemes.
+    /// Returns all schemes compiled into this build.
     pub const fn all() -> &'static [Self] {
-        &[Self::Amber, Self::Charmm]
+        &[
+            #[cfg(feature = "amber-nucleic")]
+            Self::Amber,
+            #[cfg(feature = "charmm")]
+            Self::Charmm,
+        ]
+    }
+
+    /// Returns the positions for which this scheme has nucleic acid residue data.
+    ///
+    /// Positions are listed in declaration order of [`crate::Position`].
+    pub fn positions(self) -> &'static [crate::Position] {
+        crate::generated::nucleic_positions(self.index())
+    }
+
+    /// Returns the names of all nucleic acid residues available at a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    ///
+    /// # Returns
+    ///
+    /// `&'static [&'static str]` - Residue names in sorted order (empty if the position
+    /// is not supported by this scheme).
+    pub fn residues(self, pos: impl crate::NucleicPositionArg) -> &'static [&'static str] {
+        crate::generated::nucleic_residues(self.index(), pos.into().index())
+    }
+
+    /// Resolves a nucleic acid residue to a handle for repeated lookups.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "DA", "DG", "A", "G").
+    ///
+    /// # Returns
+    ///
+    /// `Option<crate::ResidueHandle>` - Handle to the residue template if found,
+    /// otherwise `None`.
+    pub fn resolve(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &str,
+    ) -> Option<crate::ResidueHandle> {
+        crate::generated::get_nucleic_id(self.index(), pos.into().index(), residue)
+            .map(crate::ResidueHandle::new)
     }
 
     /// Returns the partial charge for an atom in a nucleic acid residue.
@@ -35,8 +97,189 @@ impl NucleicScheme {
     /// # Returns
     ///
     /// `Option<f32>` - Partial charge if found, otherwise `None`.
-    pub fn charge(self, pos: crate::Position, residue: &str, atom: &str) -> Option<f32> {
-        crate::generated::get_nucleic_charge(self.key(), pos.key(), residue, atom)
+    pub fn charge(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &str,
+        atom: &str,
+    ) -> Option<f32> {
+        self.resolve(pos, residue)?.charge(atom)
+    }
+
+    /// Returns the exact partial charge for an atom in a nucleic acid residue.
+    ///
+    /// Exact charges sum without rounding error; see [`crate::Charge`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "DA", "DG", "A", "G").
+    /// * `atom` - Atom name (e.g., "C1'", "H2'").
+    ///
+    /// # Returns
+    ///
+    /// `Option<crate::Charge>` - Exact partial charge if found, otherwise `None`.
+    pub fn exact_charge(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &str,
+        atom: &str,
+    ) -> Option<crate::Charge> {
+        self.resolve(pos, residue)?.exact_charge(atom)
+    }
+
+    /// Resolves a nucleic acid residue from a raw byte column.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name column (e.g., `b" DA"`); surrounding ASCII whitespace
+    ///   is ignored.
+    ///
+    /// # Returns
+    ///
+    /// `Option<crate::ResidueHandle>` - Handle to the residue template if found,
+    /// otherwise `None` (also for names that are not valid UTF-8).
+    pub fn resolve_bytes(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &[u8],
+    ) -> Option<crate::ResidueHandle> {
+        self.resolve(pos, crate::field::name(residue)?)
+    }
+
+    /// Returns the partial charge for an atom in a nucleic acid residue, from raw byte columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name column (e.g., `b" DA"`).
+    /// * `atom` - Atom name column (e.g., `b" P  "`).
+    ///
+    /// Surrounding ASCII whitespace in both columns is ignored.
+    ///
+    /// # Returns
+    ///
+    /// `Option<f32>` - Partial charge if found, otherwise `None` (also for names that
+    /// are not valid UTF-8).
+    pub fn charge_bytes(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &[u8],
+        atom: &[u8],
+    ) -> Option<f32> {
+        self.resolve_bytes(pos, residue)?.charge_bytes(atom)
+    }
+
+    /// Returns the charge template for a nucleic acid residue.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "DA", "DG", "A", "G").
+    ///
+    /// # Returns
+    ///
+    /// `Option<crate::ResidueTemplate>` - Ordered atoms and charges if found, otherwise `None`.
+    pub fn residue(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &str,
+    ) -> Option<crate::ResidueTemplate> {
+        self.resolve(pos, residue)
+            .map(crate::ResidueHandle::template)
+    }
+
+    /// Returns the partial charge for an atom in a nucleic acid residue, reporting why the
+    /// lookup failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "DA", "DG", "A", "G").
+    /// * `atom` - Atom name (e.g., "C1'", "H2'").
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::ChargeError`] if the position, residue or atom is not defined
+    /// by this scheme.
+    pub fn try_charge<'a>(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &'a str,
+        atom: &'a str,
+    ) -> Result<f32, crate::ChargeError<'a>> {
+        match self.charge(pos, residue, atom) {
+            Some(charge) => Ok(charge),
+            None => {
+                self.try_residue(pos, residue)?;
+                Err(crate::ChargeError::UnknownAtom { residue, atom })
+            }
+        }
+    }
+
+    /// Returns the charge template for a nucleic acid residue, reporting why the lookup
+    /// failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the residue in the chain.
+    /// * `residue` - Residue name (e.g., "DA", "DG", "A", "G").
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::ChargeError`] if the position or residue is not defined by
+    /// this scheme.
+    pub fn try_residue(
+        self,
+        pos: impl crate::NucleicPositionArg,
+        residue: &str,
+    ) -> Result<crate::ResidueTemplate, crate::ChargeError<'_>> {
+        let pos = pos.into();
+        self.residue(pos, residue).ok_or_else(|| {
+            if self.positions().contains(&pos) {
+                crate::ChargeError::UnknownResidue(residue)
+            } else {
+                crate::ChargeError::UnsupportedPosition(pos)
+            }
+        })
+    }
+
+    /// Assigns partial charges to a batch of nucleic acid atoms.
+    ///
+    /// Consecutive atoms of the same residue are resolved once, so atoms should be
+    /// grouped by residue as in a topology file.
+    ///
+    /// # Arguments
+    ///
+    /// * `atoms` - Position, residue and atom name of each atom.
+    /// * `out` - Output slice receiving one charge per atom; atoms that cannot be
+    ///   charged are set to `f32::NAN`.
+    ///
+    /// # Returns
+    ///
+    /// `crate::AssignReport` - Number of assigned and failed atoms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `atoms` and `out` have different lengths.
+    pub fn assign_into(self, atoms: &[crate::AtomKey<'_>], out: &mut [f32]) -> crate::AssignReport {
+        crate::assign::assign_with(atoms, out, |pos, res| self.resolve(pos, res))
+    }
+
+    /// Returns a lookup that accepts residue names in the given naming convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `convention` - Naming convention of the input residues.
+    pub const fn with_convention(self, convention: crate::Convention) -> crate::Aliased<Self> {
+        crate::Aliased::new(self, convention)
+    }
+
+    /// Returns a lookup that accepts names in any letter case, with surrounding
+    /// whitespace.
+    pub const fn normalized(self) -> crate::Normalized<Self> {
+        crate::Normalized::new(self)
     }
 }
 
@@ -45,15 +288,29 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "amber-nucleic")]
     fn default() {
         assert_eq!(NucleicScheme::default(), NucleicScheme::Amber);
     }
 
     #[test]
+    #[cfg(all(feature = "amber-nucleic", feature = "charmm"))]
     fn count() {
         assert_eq!(NucleicScheme::all().len(), 2);
     }
 
+    #[test]
+    fn table_order() {
+        for (i, s) in NucleicScheme::all().iter().enumerate() {
+            assert_eq!(s.index(), i);
+            assert_eq!(crate::generated::NUCLEIC_SCHEME_KEYS[s.index()], s.key());
+        }
+        assert_eq!(
+            NucleicScheme::all().len(),
+            crate::generated::NUCLEIC_SCHEME_KEYS.len()
+        );
+    }
+
     #[test]
     fn key_format() {
         for s in NucleicScheme::all() {
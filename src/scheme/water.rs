@@ -1,40 +1,67 @@
 //! Water charge schemes.
 
 /// Water charge scheme.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+///
+/// Each variant is compiled in by the cargo feature named in its description; all are
+/// enabled by default.
+/// See [scheme features](crate#scheme-features) before matching on the variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "water-tip3p", derive(Default))]
 pub enum WaterScheme {
-    /// TIP3P.
-    #[default]
+    /// TIP3P. Feature `water-tip3p`.
+    #[cfg(feature = "water-tip3p")]
+    #[cfg_attr(feature = "water-tip3p", default)]
     Tip3p,
-    /// TIP3P-FB.
+    /// TIP3P-FB. Feature `water-tip3p-fb`.
+    #[cfg(feature = "water-tip3p-fb")]
     Tip3pFb,
-    /// SPC.
+    /// SPC. Feature `water-spc`.
+    #[cfg(feature = "water-spc")]
     Spc,
-    /// SPC/E.
+    /// SPC/E. Feature `water-spc-e`.
+    #[cfg(feature = "water-spc-e")]
     SpcE,
-    /// OPC3.
+    /// OPC3. Feature `water-opc3`.
+    #[cfg(feature = "water-opc3")]
     Opc3,
 }
 
 impl WaterScheme {
+    /// Returns the index of this scheme in the generated tables.
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+
     /// Returns the internal key for this scheme.
+    #[cfg(test)]
     pub(crate) const fn key(self) -> &'static str {
         match self {
+            #[cfg(feature = "water-tip3p")]
             Self::Tip3p => "tip3p",
+            #[cfg(feature = "water-tip3p-fb")]
             Self::Tip3pFb => "tip3p-fb",
+            #[cfg(feature = "water-spc")]
             Self::Spc => "spc",
+            #[cfg(feature = "water-spc-e")]
             Self::SpcE => "spc-e",
+            #[cfg(feature = "water-opc3")]
             Self::Opc3 => "opc3",
         }
     }
 
-    /// Returns all available schemes.
+    /// Returns all schemes compiled into this build.
     pub const fn all() -> &'static [Self] {
         &[
+            #[cfg(feature = "water-tip3p")]
             Self::Tip3p,
+            #[cfg(feature = "water-tip3p-fb")]
             Self::Tip3pFb,
+            #[cfg(feature = "water-spc")]
             Self::Spc,
+            #[cfg(feature = "water-spc-e")]
             Self::SpcE,
+            #[cfg(feature = "water-opc3")]
             Self::Opc3,
         ]
     }
@@ -45,7 +72,22 @@ impl WaterScheme {
     ///
     /// `Option<crate::WaterCharges>` - Partial charges if found, otherwise `None`.
     pub fn charges(self) -> Option<crate::WaterCharges> {
-        crate::generated::get_water_charges(self.key())
+        crate::generated::get_water_charges(self.index())
+    }
+
+    /// Returns a lookup that accepts residue names in the given naming convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `convention` - Naming convention of the input residues.
+    pub const fn with_convention(self, convention: crate::Convention) -> crate::Aliased<Self> {
+        crate::Aliased::new(self, convention)
+    }
+
+    /// Returns a lookup that accepts names in any letter case, with surrounding
+    /// whitespace.
+    pub const fn normalized(self) -> crate::Normalized<Self> {
+        crate::Normalized::new(self)
     }
 }
 
@@ -54,11 +96,19 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "water-tip3p")]
     fn default() {
         assert_eq!(WaterScheme::default(), WaterScheme::Tip3p);
     }
 
     #[test]
+    #[cfg(all(
+        feature = "water-tip3p",
+        feature = "water-tip3p-fb",
+        feature = "water-spc",
+        feature = "water-spc-e",
+        feature = "water-opc3"
+    ))]
     fn count() {
         assert_eq!(WaterScheme::all().len(), 5);
     }
@@ -0,0 +1,87 @@
+#![cfg(all(feature = "amber-ffsb", feature = "amber-nucleic", feature = "charmm"))]
+
+use ffcharge::{AssignReport, AtomKey, NucleicScheme, Position, ProteinScheme};
+
+// =============================================================================
+// Protein
+// =============================================================================
+
+#[test]
+fn protein_chain() {
+    let scheme = ProteinScheme::Charmm;
+    let mut keys = Vec::new();
+    for (pos, res) in [
+        (Position::NTerminal, "MET"),
+        (Position::Middle, "HID"),
+        (Position::CTerminal, "GLY"),
+    ] {
+        let t = scheme.residue(pos, res).expect("Missing residue");
+        keys.extend(t.atom_names().map(|atom| AtomKey::new(pos, res, atom)));
+    }
+
+    let mut out = vec![0.0; keys.len()];
+    let report = scheme.assign_into(&keys, &mut out);
+    assert!(report.is_complete());
+    assert_eq!(report.assigned, keys.len());
+    for (key, charge) in keys.iter().zip(&out) {
+        assert_eq!(
+            scheme.charge(key.position, key.residue, key.atom),
+            Some(*charge)
+        );
+    }
+}
+
+#[test]
+fn protein_failures() {
+    let keys = [
+        AtomKey::new(Position::Middle, "SER", "OG"),
+        AtomKey::new(Position::FivePrime, "SER", "OG"),
+        AtomKey::new(Position::Middle, "SER", "SG"),
+        AtomKey::new(Position::Middle, "SER", "HG"),
+    ];
+    let mut out = [0.0; 4];
+    let report = ProteinScheme::AmberFFSB.assign_into(&keys, &mut out);
+    assert_eq!(
+        report,
+        AssignReport {
+            assigned: 2,
+            failed: 2,
+            first_failure: Some(1),
+        }
+    );
+    assert!(!out[0].is_nan() && !out[3].is_nan());
+    assert!(out[1].is_nan() && out[2].is_nan());
+}
+
+// =============================================================================
+// Nucleic Acid
+// =============================================================================
+
+#[test]
+fn nucleic_interleaved_residues() {
+    let scheme = NucleicScheme::Amber;
+    let keys = [
+        AtomKey::new(Position::FivePrime, "DA", "HO5'"),
+        AtomKey::new(Position::Middle, "DC", "P"),
+        AtomKey::new(Position::FivePrime, "DA", "N9"),
+        AtomKey::new(Position::ThreePrime, "DG", "HO3'"),
+    ];
+    let mut out = [0.0; 4];
+    let report = scheme.assign_into(&keys, &mut out);
+    assert!(report.is_complete());
+    for (key, charge) in keys.iter().zip(out) {
+        assert_eq!(
+            scheme.charge(key.position, key.residue, key.atom),
+            Some(charge)
+        );
+    }
+}
+
+#[test]
+fn nucleic_rejects_protein() {
+    let keys = [AtomKey::new(Position::Middle, "ALA", "CA")];
+    let mut out = [0.0];
+    let report = NucleicScheme::Charmm.assign_into(&keys, &mut out);
+    assert_eq!(report.failed, 1);
+    assert!(out[0].is_nan());
+}
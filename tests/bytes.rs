@@ -0,0 +1,100 @@
+#![cfg(all(
+    feature = "amber-ffsb",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "ions"
+))]
+
+use ffcharge::{IonScheme, NucleicScheme, Position, ProteinScheme};
+
+/// ATOM records in PDB v3 column layout.
+const RECORDS: &[&[u8]] = &[
+    b"ATOM      1  N   ALA A   1      11.104   6.134  -6.504  1.00  0.00           N",
+    b"ATOM      2  CA  ALA A   1      11.639   6.071  -5.147  1.00  0.00           C",
+    b"ATOM      3  HB1 ALA A   1      12.200   4.000  -4.500  1.00  0.00           H",
+    b"ATOM      4  OXT GLY A   2      10.000   5.000  -3.000  1.00  0.00           O",
+];
+
+/// Returns the atom name (columns 13-16) and residue name (columns 18-20) of a record.
+fn columns(record: &[u8]) -> (&[u8], &[u8]) {
+    (&record[12..16], &record[17..20])
+}
+
+// =============================================================================
+// Protein
+// =============================================================================
+
+#[test]
+fn pdb_columns() {
+    let scheme = ProteinScheme::AmberFFSB;
+    for record in &RECORDS[..3] {
+        let (atom, residue) = columns(record);
+        let expected = scheme.charge(
+            Position::NTerminal,
+            core::str::from_utf8(residue).unwrap().trim(),
+            core::str::from_utf8(atom).unwrap().trim(),
+        );
+        assert!(expected.is_some());
+        assert_eq!(
+            scheme.charge_bytes(Position::NTerminal, residue, atom),
+            expected
+        );
+    }
+    let (atom, residue) = columns(RECORDS[3]);
+    assert_eq!(
+        scheme.charge_bytes(Position::CTerminal, residue, atom),
+        scheme.charge(Position::CTerminal, "GLY", "OXT")
+    );
+}
+
+#[test]
+fn handle_bytes() {
+    let h = ProteinScheme::Charmm
+        .resolve_bytes(Position::Middle, b" SER")
+        .expect("Missing: Charmm/m/SER");
+    let id = h.atom_index_bytes(b" OG ").expect("Missing: OG");
+    assert_eq!(h.atom_index("OG"), Some(id));
+    assert_eq!(h.charge_bytes(b"OG  "), h.charge("OG"));
+}
+
+#[test]
+fn invalid_bytes() {
+    let scheme = ProteinScheme::AmberFFSB;
+    assert_eq!(
+        scheme.charge_bytes(Position::Middle, b"AL\xff", b" CA "),
+        None
+    );
+    assert_eq!(
+        scheme.charge_bytes(Position::Middle, b"ALA", b" C\x80 "),
+        None
+    );
+    assert_eq!(scheme.charge_bytes(Position::Middle, b"   ", b" CA "), None);
+    assert_eq!(
+        scheme.charge_bytes(Position::Middle, b"A LA", b" CA "),
+        None
+    );
+}
+
+// =============================================================================
+// Nucleic Acid and Ion
+// =============================================================================
+
+#[test]
+fn nucleic_bytes() {
+    assert_eq!(
+        NucleicScheme::Amber.charge_bytes(Position::Middle, b" DA", b" C1'"),
+        NucleicScheme::Amber.charge(Position::Middle, "DA", "C1'")
+    );
+    assert!(
+        NucleicScheme::Amber
+            .charge_bytes(Position::Middle, b" DA", b" C1'")
+            .is_some()
+    );
+}
+
+#[test]
+fn ion_bytes() {
+    assert_eq!(IonScheme::Classic.charge_bytes(b" NA"), Some(1.0));
+    assert_eq!(IonScheme::Classic.charge_bytes(b"CL "), Some(-1.0));
+    assert_eq!(IonScheme::Classic.charge_bytes(b"\xfe\xff"), None);
+}
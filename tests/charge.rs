@@ -1,31 +1,35 @@
-use ffcharge::{IonScheme, NucleicScheme, Position, ProteinScheme, WaterScheme};
-
-// =============================================================================
-// Generated Test Data
-// =============================================================================
-
-mod generated {
-    include!(concat!(env!("OUT_DIR"), "/codegen_test.rs"));
-}
+use ffcharge::{Charge, IonScheme, NucleicScheme, Position, ProteinScheme, WaterScheme};
 
 // =============================================================================
 // Test Utilities
 // =============================================================================
 
+#[cfg(any(
+    feature = "amber-ffsb",
+    feature = "amber-ff03",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "water-tip3p",
+    feature = "water-tip3p-fb",
+    feature = "water-spc",
+    feature = "water-spc-e",
+    feature = "water-opc3",
+    feature = "ions"
+))]
 const EPSILON: f32 = 1e-5;
 
-fn assert_charge_eq(actual: f32, expected: f32, context: &str) {
-    let diff = (actual - expected).abs();
-    assert!(
-        diff < EPSILON,
-        "{}: expected {:.7}, got {:.7} (diff: {:.7})",
-        context,
-        expected,
-        actual,
-        diff
-    );
-}
-
+#[cfg(any(
+    feature = "amber-ffsb",
+    feature = "amber-ff03",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "water-tip3p",
+    feature = "water-tip3p-fb",
+    feature = "water-spc",
+    feature = "water-spc-e",
+    feature = "water-opc3",
+    feature = "ions"
+))]
 fn assert_charge_is_int(actual: f32, expected: i32, context: &str) {
     let diff = (actual - expected as f32).abs();
     assert!(
@@ -38,50 +42,18 @@ fn assert_charge_is_int(actual: f32, expected: i32, context: &str) {
     );
 }
 
-fn protein_total_charge(scheme: ProteinScheme, pos: Position, residue: &str) -> Option<f32> {
-    let scheme_key = match scheme {
-        ProteinScheme::AmberFFSB => "amber-ffsb",
-        ProteinScheme::AmberFF03 => "amber-ff03",
-        ProteinScheme::Charmm => "charmm",
-    };
-    let pos_key = match pos {
-        Position::NTerminal => "n",
-        Position::NTerminalDeprotonated => "n-",
-        Position::CTerminal => "c",
-        Position::CTerminalProtonated => "c+",
-        Position::FivePrime => "5",
-        Position::ThreePrime => "3",
-        Position::Middle => "m",
-    };
-    let atoms = generated::get_protein_atoms(scheme_key, pos_key, residue)?;
-    Some(atoms.iter().map(|(_, c)| c).sum())
-}
-
-fn nucleic_total_charge(scheme: NucleicScheme, pos: Position, residue: &str) -> Option<f32> {
-    let scheme_key = match scheme {
-        NucleicScheme::Amber => "amber",
-        NucleicScheme::Charmm => "charmm",
-    };
-    let pos_key = match pos {
-        Position::NTerminal => "n",
-        Position::NTerminalDeprotonated => "n-",
-        Position::CTerminal => "c",
-        Position::CTerminalProtonated => "c+",
-        Position::FivePrime => "5",
-        Position::ThreePrime => "3",
-        Position::Middle => "m",
-    };
-    let atoms = generated::get_nucleic_atoms(scheme_key, pos_key, residue)?;
-    Some(atoms.iter().map(|(_, c)| c).sum())
-}
-
 // =============================================================================
 // Protein Tests
 // =============================================================================
 
+#[cfg(any(feature = "amber-ffsb", feature = "amber-ff03", feature = "charmm"))]
 mod protein {
     use super::*;
 
+    fn protein_total_charge(scheme: ProteinScheme, pos: Position, residue: &str) -> Option<f32> {
+        scheme.residue(pos, residue).map(|t| t.net_charge())
+    }
+
     macro_rules! test_protein_middle {
         ($scheme:ident, $residue:ident, $expected:expr) => {
             pastey::paste! {
@@ -136,6 +108,26 @@ mod protein {
                         .expect(concat!("Missing: ", stringify!($scheme), "/m/", stringify!($residue)));
                     assert_charge_is_int(cp, m.round() as i32, concat!(stringify!($scheme), "/c+/", stringify!($residue)));
                 }
+
+                #[test]
+                #[cfg(feature = "derived-templates")]
+                fn [<$scheme:lower _ $residue:lower _nc_terminal>]() {
+                    let nc = protein_total_charge(ProteinScheme::$scheme, Position::NCTerminal, stringify!($residue))
+                        .expect(concat!("Missing: ", stringify!($scheme), "/nc/", stringify!($residue)));
+                    let m = protein_total_charge(ProteinScheme::$scheme, Position::Middle, stringify!($residue))
+                        .expect(concat!("Missing: ", stringify!($scheme), "/m/", stringify!($residue)));
+                    assert_charge_is_int(nc, m.round() as i32, concat!(stringify!($scheme), "/nc/", stringify!($residue)));
+                }
+
+                #[test]
+                #[cfg(feature = "derived-templates")]
+                fn [<$scheme:lower _ $residue:lower _nc_terminal_neutral>]() {
+                    let nc = protein_total_charge(ProteinScheme::$scheme, Position::NCTerminalNeutral, stringify!($residue))
+                        .expect(concat!("Missing: ", stringify!($scheme), "/n-c+/", stringify!($residue)));
+                    let m = protein_total_charge(ProteinScheme::$scheme, Position::Middle, stringify!($residue))
+                        .expect(concat!("Missing: ", stringify!($scheme), "/m/", stringify!($residue)));
+                    assert_charge_is_int(nc, m.round() as i32, concat!(stringify!($scheme), "/n-c+/", stringify!($residue)));
+                }
             }
         };
     }
@@ -147,6 +139,7 @@ mod protein {
         };
     }
 
+    #[cfg(feature = "amber-ffsb")]
     mod amber_ffsb {
         use super::*;
         test_protein_residue!(AmberFFSB, ALA, 0);
@@ -180,6 +173,7 @@ mod protein {
         test_protein_residue!(AmberFFSB, TYM, -1);
     }
 
+    #[cfg(feature = "amber-ff03")]
     mod amber_ff03 {
         use super::*;
         test_protein_residue!(AmberFF03, ALA, 0);
@@ -213,6 +207,7 @@ mod protein {
         test_protein_residue!(AmberFF03, TYM, -1);
     }
 
+    #[cfg(feature = "charmm")]
     mod charmm {
         use super::*;
         test_protein_residue!(Charmm, ALA, 0);
@@ -251,9 +246,14 @@ mod protein {
 // Nucleic Acid Tests
 // =============================================================================
 
+#[cfg(any(feature = "amber-nucleic", feature = "charmm"))]
 mod nucleic {
     use super::*;
 
+    fn nucleic_total_charge(scheme: NucleicScheme, pos: Position, residue: &str) -> Option<f32> {
+        scheme.residue(pos, residue).map(|t| t.net_charge())
+    }
+
     macro_rules! test_nucleic_middle {
         ($scheme:ident, $residue:ident, $expected:expr) => {
             pastey::paste! {
@@ -285,13 +285,29 @@ mod nucleic {
         };
     }
 
+    macro_rules! test_nucleic_single {
+        ($scheme:ident, $residue:ident) => {
+            pastey::paste! {
+                #[test]
+                #[cfg(feature = "derived-templates")]
+                fn [<$scheme:lower _ $residue:lower _five_three_prime>]() {
+                    let charge = nucleic_total_charge(NucleicScheme::$scheme, Position::FiveThreePrime, stringify!($residue))
+                        .expect(concat!("Missing: ", stringify!($scheme), "/53/", stringify!($residue)));
+                    assert_charge_is_int(charge, 0, concat!(stringify!($scheme), "/53/", stringify!($residue)));
+                }
+            }
+        };
+    }
+
     macro_rules! test_nucleic_residue {
         ($scheme:ident, $residue:ident, $expected:expr) => {
             test_nucleic_middle!($scheme, $residue, $expected);
             test_nucleic_terminal_sum!($scheme, $residue, $expected);
+            test_nucleic_single!($scheme, $residue);
         };
     }
 
+    #[cfg(feature = "amber-nucleic")]
     mod amber_dna {
         use super::*;
         test_nucleic_residue!(Amber, DA, -1);
@@ -301,6 +317,7 @@ mod nucleic {
         test_nucleic_residue!(Amber, DI, -1);
     }
 
+    #[cfg(feature = "amber-nucleic")]
     mod amber_rna {
         use super::*;
         test_nucleic_residue!(Amber, A, -1);
@@ -310,6 +327,7 @@ mod nucleic {
         test_nucleic_residue!(Amber, I, -1);
     }
 
+    #[cfg(feature = "charmm")]
     mod charmm_dna {
         use super::*;
         test_nucleic_residue!(Charmm, DA, -1);
@@ -319,6 +337,7 @@ mod nucleic {
         test_nucleic_residue!(Charmm, DI, -1);
     }
 
+    #[cfg(feature = "charmm")]
     mod charmm_rna {
         use super::*;
         test_nucleic_residue!(Charmm, A, -1);
@@ -333,9 +352,28 @@ mod nucleic {
 // Water Tests
 // =============================================================================
 
+#[cfg(any(
+    feature = "water-tip3p",
+    feature = "water-tip3p-fb",
+    feature = "water-spc",
+    feature = "water-spc-e",
+    feature = "water-opc3"
+))]
 mod water {
     use super::*;
 
+    fn assert_charge_eq(actual: f32, expected: f32, context: &str) {
+        let diff = (actual - expected).abs();
+        assert!(
+            diff < EPSILON,
+            "{}: expected {:.7}, got {:.7} (diff: {:.7})",
+            context,
+            expected,
+            actual,
+            diff
+        );
+    }
+
     macro_rules! test_water_model {
         ($scheme:ident) => {
             pastey::paste! {
@@ -354,10 +392,15 @@ mod water {
         };
     }
 
+    #[cfg(feature = "water-tip3p")]
     test_water_model!(Tip3p);
+    #[cfg(feature = "water-tip3p-fb")]
     test_water_model!(Tip3pFb);
+    #[cfg(feature = "water-spc")]
     test_water_model!(Spc);
+    #[cfg(feature = "water-spc-e")]
     test_water_model!(SpcE);
+    #[cfg(feature = "water-opc3")]
     test_water_model!(Opc3);
 }
 
@@ -365,6 +408,7 @@ mod water {
 // Ion Tests
 // =============================================================================
 
+#[cfg(feature = "ions")]
 mod ion {
     use super::*;
 
@@ -469,3 +513,167 @@ mod ion {
     test_ion!(BR, -1);
     test_ion!(IOD, -1);
 }
+
+// =============================================================================
+// Exact Charge Tests
+// =============================================================================
+
+mod exact {
+    use super::*;
+
+    /// Largest residual of a published template net charge, in units of 1e-7 e.
+    const MAX_RESIDUAL: i64 = 100;
+
+    fn assert_near_integer(q: Charge, context: &str) {
+        let residual = (q - q.round()).units();
+        assert!(
+            residual.abs() <= MAX_RESIDUAL,
+            "{}: net charge {}",
+            context,
+            q
+        );
+    }
+
+    #[test]
+    fn protein_templates_are_near_integral() {
+        for &scheme in ProteinScheme::all() {
+            for &pos in scheme.positions() {
+                for &res in scheme.residues(pos) {
+                    let t = scheme.residue(pos, res).unwrap();
+                    let context = format!("{:?}/{:?}/{}", scheme, pos, res);
+                    assert_near_integer(t.exact_net_charge(), &context);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nucleic_templates_are_near_integral() {
+        for &scheme in NucleicScheme::all() {
+            for &res in scheme.residues(Position::Middle) {
+                let net = |pos| scheme.residue(pos, res).map(|t| t.exact_net_charge());
+                let context = format!("{:?}/{}", scheme, res);
+                assert_near_integer(net(Position::Middle).unwrap(), &context);
+                if let Some(q) = net(Position::FiveThreePrime) {
+                    assert_near_integer(q, &context);
+                }
+                if let (Some(five), Some(three)) =
+                    (net(Position::FivePrime), net(Position::ThreePrime))
+                {
+                    assert_near_integer(five + three, &context);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "amber-ffsb")]
+    fn amber_ffsb_middle_templates_are_integral() {
+        let scheme = ProteinScheme::AmberFFSB;
+        for &res in scheme.residues(Position::Middle) {
+            let q = scheme
+                .residue(Position::Middle, res)
+                .unwrap()
+                .exact_net_charge();
+            assert!(q.is_integer(), "AmberFFSB/m/{}: net charge {}", res, q);
+        }
+    }
+
+    #[test]
+    fn exact_charges_round_to_table_values() {
+        for &scheme in ProteinScheme::all() {
+            for &pos in scheme.positions() {
+                for &res in scheme.residues(pos) {
+                    let t = scheme.residue(pos, res).unwrap();
+                    for ((atom, charge), (_, exact)) in t.iter().zip(t.exact_charges()) {
+                        assert_eq!(
+                            exact.to_f32(),
+                            charge,
+                            "{:?}/{:?}/{}/{}",
+                            scheme,
+                            pos,
+                            res,
+                            atom
+                        );
+                        assert_eq!(scheme.exact_charge(pos, res, atom), Some(exact));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "amber-ffsb", feature = "amber-ff03"))]
+    fn peptide_sum_is_exact() {
+        let scheme = ProteinScheme::AmberFFSB;
+        let mut chain = vec![(Position::NTerminal, "ALA")];
+        chain.extend(
+            ["ARG", "LYS", "GLU", "ASP"]
+                .repeat(50)
+                .into_iter()
+                .map(|r| (Position::Middle, r)),
+        );
+        chain.push((Position::CTerminal, "GLY"));
+        let total: Charge = chain
+            .iter()
+            .map(|&(pos, res)| scheme.residue(pos, res).unwrap().exact_net_charge())
+            .sum();
+        assert_eq!(total, Charge::ZERO);
+
+        let atoms: Option<Charge> = chain
+            .iter()
+            .flat_map(|&(pos, res)| {
+                let t = scheme.residue(pos, res).unwrap();
+                t.atom_names()
+                    .map(move |a| scheme.exact_charge(pos, res, a))
+            })
+            .sum();
+        assert_eq!(atoms, Some(Charge::ZERO));
+
+        let ff03 = ffcharge::NetCharge::of(
+            [(Position::NTerminal, "SER"), (Position::CTerminal, "LYS")]
+                .map(|(pos, res)| ProteinScheme::AmberFF03.residue(pos, res).unwrap()),
+        );
+        assert_eq!(ff03.formal, 1);
+        assert!(!ff03.is_integral());
+        assert!(ff03.residual.units().abs() <= 2 * MAX_RESIDUAL);
+    }
+
+    /// Largest rounding error of a water or ion charge converted from `f32`, in units of
+    /// 1e-7 e.
+    const F32_TOLERANCE: i64 = 1;
+
+    #[test]
+    fn water_and_ion_charges_convert_within_tolerance() {
+        for &scheme in WaterScheme::all() {
+            let c = scheme.charges().unwrap();
+            let total: Charge = [c.o, c.h1, c.h2].map(Charge::from_f32).iter().sum();
+            assert!(
+                total.units().abs() <= 3 * F32_TOLERANCE,
+                "{:?}: net charge {}",
+                scheme,
+                total
+            );
+        }
+        for &scheme in IonScheme::all() {
+            for &res in scheme.residues() {
+                let q = Charge::from_f32(scheme.charge(res).unwrap());
+                assert!(q.is_integer(), "{:?}/{}", scheme, res);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "water-tip3p-fb")]
+    fn tip3p_fb_charges_round_to_published_values() {
+        let c = WaterScheme::Tip3pFb.charges().unwrap();
+        for (charge, published) in [
+            (c.o, -0.848448690103),
+            (c.h1, 0.4242243450515),
+            (c.h2, 0.4242243450515),
+        ] {
+            let error = Charge::from_f32(charge) - Charge::from_f64(published);
+            assert!(error.units().abs() <= F32_TOLERANCE, "{}", charge);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use ffcharge::{NucleicScheme, ProteinScheme, ResidueClass, classify};
+
+#[test]
+fn every_protein_residue() {
+    for &scheme in ProteinScheme::all() {
+        for &pos in scheme.positions() {
+            for res in scheme.residues(pos) {
+                assert_eq!(classify(res), Some(ResidueClass::Protein), "{}", res);
+            }
+        }
+    }
+}
+
+#[test]
+fn every_nucleic_residue() {
+    for &scheme in NucleicScheme::all() {
+        for &pos in scheme.positions() {
+            for res in scheme.residues(pos) {
+                let class = classify(res).expect("Missing class");
+                assert!(class.is_nucleic(), "{}", res);
+                let rna = scheme.residue(pos, res).unwrap().contains("O2'");
+                assert_eq!(class == ResidueClass::Rna, rna, "{}", res);
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(any(feature = "amber-nucleic", feature = "charmm"))]
+fn dna_and_rna() {
+    for res in ["DA", "DC", "DG", "DT"] {
+        assert_eq!(classify(res), Some(ResidueClass::Dna));
+    }
+    for res in ["A", "C", "G", "U"] {
+        assert_eq!(classify(res), Some(ResidueClass::Rna));
+    }
+}
+
+#[test]
+#[cfg(feature = "water-tip3p")]
+fn water() {
+    assert_eq!(classify("HOH"), Some(ResidueClass::Water));
+}
+
+#[test]
+#[cfg(feature = "ions")]
+fn ions() {
+    for res in ffcharge::IonScheme::Classic.residues() {
+        assert_eq!(classify(res), Some(ResidueClass::Ion), "{}", res);
+    }
+}
+
+#[test]
+fn unknown_residues() {
+    for res in ["LIG", "ATP", "", "ala", "WAT"] {
+        assert_eq!(classify(res), None, "{}", res);
+    }
+}
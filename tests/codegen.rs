@@ -0,0 +1,4 @@
+//! Runs the unit tests of the charge table code generator.
+
+#[path = "../codegen/mod.rs"]
+mod codegen;
@@ -0,0 +1,246 @@
+#![cfg(all(
+    feature = "amber-ffsb",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "water-tip3p",
+    feature = "ions"
+))]
+
+use ffcharge::{
+    Convention, IonScheme, NucleicScheme, Position, ProteinPosition, ProteinScheme, WaterScheme,
+};
+
+// =============================================================================
+// Protein Aliases
+// =============================================================================
+
+#[test]
+fn charmm_histidine() {
+    let scheme = ProteinScheme::Charmm.with_convention(Convention::Charmm);
+    for (alias, canonical) in [("HSD", "HID"), ("HSE", "HIE"), ("HSP", "HIP")] {
+        assert_eq!(
+            scheme.charge(Position::Middle, alias, "NE2"),
+            ProteinScheme::Charmm.charge(Position::Middle, canonical, "NE2")
+        );
+        assert!(scheme.charge(Position::Middle, alias, "NE2").is_some());
+    }
+}
+
+#[test]
+fn protonation_variants() {
+    let cases = [
+        (Convention::Charmm, "ASPP", "ASH"),
+        (Convention::Charmm, "GLUP", "GLH"),
+        (Convention::Charmm, "LSN", "LYN"),
+        (Convention::Gromacs, "CYS2", "CYX"),
+        (Convention::Gromacs, "LYSN", "LYN"),
+        (Convention::Gromacs, "HISH", "HIP"),
+    ];
+    for (convention, alias, canonical) in cases {
+        let scheme = ProteinScheme::AmberFFSB.with_convention(convention);
+        let t = scheme
+            .residue(ProteinPosition::Middle, alias)
+            .unwrap_or_else(|| panic!("Missing: {:?}/{}", convention, alias));
+        assert_eq!(
+            Some(t),
+            ProteinScheme::AmberFFSB.residue(Position::Middle, canonical)
+        );
+    }
+}
+
+#[test]
+fn amber_terminal_prefixes() {
+    let scheme = ProteinScheme::AmberFFSB.with_convention(Convention::Amber);
+    assert_eq!(
+        scheme.charge(Position::Middle, "NALA", "H3"),
+        ProteinScheme::AmberFFSB.charge(Position::NTerminal, "ALA", "H3")
+    );
+    assert_eq!(
+        scheme.charge(Position::Middle, "CGLY", "OXT"),
+        ProteinScheme::AmberFFSB.charge(Position::CTerminal, "GLY", "OXT")
+    );
+}
+
+#[test]
+fn canonical_names_pass_through() {
+    for &convention in Convention::all() {
+        let scheme = ProteinScheme::AmberFFSB.with_convention(convention);
+        assert_eq!(
+            scheme.charge(Position::Middle, "TRP", "CA"),
+            ProteinScheme::AmberFFSB.charge(Position::Middle, "TRP", "CA")
+        );
+    }
+}
+
+// =============================================================================
+// Nucleic Acid Aliases
+// =============================================================================
+
+#[test]
+fn amber_nucleic_termini() {
+    let scheme = NucleicScheme::Amber.with_convention(Convention::Amber);
+    assert_eq!(
+        scheme.residue(Position::Middle, "DA5"),
+        NucleicScheme::Amber.residue(Position::FivePrime, "DA")
+    );
+    assert_eq!(
+        scheme.residue(Position::Middle, "RA3"),
+        NucleicScheme::Amber.residue(Position::ThreePrime, "A")
+    );
+    #[cfg(feature = "derived-templates")]
+    assert!(
+        scheme.residue(Position::Middle, "DTN").is_some_and(
+            |t| Some(t) == NucleicScheme::Amber.residue(Position::FiveThreePrime, "DT")
+        )
+    );
+    assert!(scheme.residue(Position::Middle, "RU").is_some());
+}
+
+#[test]
+fn charmm_bases() {
+    let scheme = NucleicScheme::Charmm.with_convention(Convention::Charmm);
+    for (alias, canonical) in [("URA", "U"), ("THY", "DT")] {
+        assert_eq!(
+            scheme.residue(Position::Middle, alias),
+            NucleicScheme::Charmm.residue(Position::Middle, canonical),
+        );
+        assert!(scheme.residue(Position::Middle, alias).is_some());
+    }
+}
+
+#[test]
+fn charmm_ambiguous_bases() {
+    // ADE, CYT and GUA name both DNA and RNA residues in CHARMM.
+    for alias in ["ADE", "CYT", "GUA"] {
+        assert_eq!(
+            Convention::Charmm.residue(alias, Position::Middle),
+            (alias, Position::Middle)
+        );
+        assert!(
+            NucleicScheme::Charmm
+                .with_convention(Convention::Charmm)
+                .residue(Position::Middle, alias)
+                .is_none()
+        );
+    }
+}
+
+// =============================================================================
+// Atom Aliases
+// =============================================================================
+
+#[test]
+fn charmm_atom_names() {
+    let scheme = ProteinScheme::Charmm.with_convention(Convention::Charmm);
+    let canonical = ProteinScheme::Charmm;
+    let cases = [
+        (Position::Middle, "ALA", "HN", "H"),
+        (Position::NTerminal, "ALA", "HT1", "H1"),
+        (Position::CTerminal, "GLY", "OT1", "O"),
+        (Position::CTerminal, "GLY", "OT2", "OXT"),
+        (Position::Middle, "ILE", "CD", "CD1"),
+        (Position::Middle, "ILE", "HD1", "HD11"),
+        (Position::Middle, "SER", "HB1", "HB2"),
+        (Position::Middle, "SER", "HB2", "HB3"),
+    ];
+    for (pos, residue, alias, atom) in cases {
+        assert_eq!(
+            scheme.charge(pos, residue, alias),
+            canonical.charge(pos, residue, atom),
+            "{}/{}",
+            residue,
+            alias
+        );
+        assert!(scheme.charge(pos, residue, alias).is_some());
+    }
+}
+
+#[test]
+fn residue_and_atom_aliases_combine() {
+    let scheme = ProteinScheme::Charmm.with_convention(Convention::Charmm);
+    assert_eq!(
+        scheme.charge(Position::Middle, "HSD", "HN"),
+        ProteinScheme::Charmm.charge(Position::Middle, "HID", "H")
+    );
+    assert!(scheme.charge(Position::Middle, "HSD", "HN").is_some());
+}
+
+#[test]
+fn pdbv2_atom_names() {
+    let protein = ProteinScheme::AmberFFSB.with_convention(Convention::PdbV2);
+    assert_eq!(
+        protein.charge(Position::Middle, "SER", "1HB"),
+        ProteinScheme::AmberFFSB.charge(Position::Middle, "SER", "HB2")
+    );
+    assert!(protein.charge(Position::Middle, "SER", "1HB").is_some());
+
+    let nucleic = NucleicScheme::Amber.with_convention(Convention::PdbV2);
+    for (alias, atom) in [("O1P", "OP1"), ("1H5*", "H5'"), ("2H5*", "H5''")] {
+        assert_eq!(
+            nucleic.charge(Position::Middle, "DA", alias),
+            NucleicScheme::Amber.charge(Position::Middle, "DA", atom)
+        );
+        assert!(nucleic.charge(Position::Middle, "DA", alias).is_some());
+    }
+}
+
+#[test]
+fn canonical_atom_names_pass_through() {
+    for &convention in Convention::all() {
+        let scheme = NucleicScheme::Amber.with_convention(convention);
+        assert_eq!(
+            scheme.charge(Position::Middle, "DA", "N9"),
+            NucleicScheme::Amber.charge(Position::Middle, "DA", "N9")
+        );
+    }
+}
+
+// =============================================================================
+// Water and Ion Aliases
+// =============================================================================
+
+#[test]
+fn water_names() {
+    let tip3p = WaterScheme::Tip3p.charges();
+    for (convention, alias) in [
+        (Convention::Amber, "WAT"),
+        (Convention::Charmm, "TIP3"),
+        (Convention::Gromacs, "SOL"),
+        (Convention::Pdb, "HOH"),
+    ] {
+        assert_eq!(
+            WaterScheme::Tip3p
+                .with_convention(convention)
+                .charges(alias),
+            tip3p
+        );
+    }
+    assert!(
+        WaterScheme::Tip3p
+            .with_convention(Convention::Pdb)
+            .charges("NA")
+            .is_none()
+    );
+}
+
+#[test]
+fn ion_names() {
+    let cases = [
+        (Convention::Amber, "Na+", 1.0),
+        (Convention::Amber, "Cl-", -1.0),
+        (Convention::Charmm, "SOD", 1.0),
+        (Convention::Charmm, "POT", 1.0),
+        (Convention::Charmm, "CLA", -1.0),
+        (Convention::Charmm, "CAL", 2.0),
+        (Convention::Gromacs, "NA", 1.0),
+    ];
+    for (convention, alias, expected) in cases {
+        assert_eq!(
+            IonScheme::Classic.with_convention(convention).charge(alias),
+            Some(expected),
+            "{:?}/{}",
+            convention,
+            alias
+        );
+    }
+}
@@ -0,0 +1,161 @@
+#![cfg(all(
+    feature = "std",
+    feature = "amber-ffsb",
+    feature = "amber-ff03",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "water-tip3p",
+    feature = "ions"
+))]
+
+use std::collections::BTreeMap;
+
+use ffcharge::{
+    ChargeRegistry, CsvErrorKind, CustomScheme, IonScheme, NucleicScheme, Position, ProteinScheme,
+    ResidueChargeProvider, WaterScheme,
+};
+
+const CHARGES: &str = include_str!("../data/charges.csv");
+
+/// Parses the derived templates, if they are compiled into the built-in schemes.
+fn derived_schemes() -> BTreeMap<String, CustomScheme> {
+    if cfg!(feature = "derived-templates") {
+        CustomScheme::parse_csv(include_str!("../data/derived.csv"))
+            .expect("Failed to parse data/derived.csv")
+    } else {
+        BTreeMap::new()
+    }
+}
+
+/// Parses the extra data fixture, if it is compiled into the built-in schemes.
+fn extra_schemes() -> BTreeMap<String, CustomScheme> {
+    if cfg!(ffcharge_extra_data) {
+        CustomScheme::parse_csv(include_str!("data/extra.csv"))
+            .expect("Failed to parse tests/data/extra.csv")
+    } else {
+        BTreeMap::new()
+    }
+}
+
+// =============================================================================
+// Bundled Data
+// =============================================================================
+
+#[test]
+fn bundled_data_matches_builtin_schemes() {
+    let schemes = CustomScheme::parse_csv(CHARGES).expect("Failed to parse data/charges.csv");
+    let derived = derived_schemes();
+    let extra = extra_schemes();
+
+    let protein = [
+        ("amber-ffsb", ProteinScheme::AmberFFSB),
+        ("amber-ff03", ProteinScheme::AmberFF03),
+        ("charmm", ProteinScheme::Charmm),
+    ];
+    for (key, scheme) in protein {
+        let custom = &schemes[key];
+        for &pos in scheme.positions() {
+            for residue in scheme.residues(pos) {
+                let builtin: Vec<_> = scheme.residue_atoms(pos, residue).unwrap().collect();
+                let loaded: Vec<_> = custom
+                    .residue_atoms(pos, residue)
+                    .or_else(|| derived.get(key)?.residue_atoms(pos, residue))
+                    .or_else(|| extra.get(key)?.residue_atoms(pos, residue))
+                    .unwrap()
+                    .collect();
+                assert_eq!(builtin, loaded, "{key} {pos:?} {residue}");
+            }
+        }
+    }
+
+    let nucleic = [
+        ("amber", NucleicScheme::Amber),
+        ("charmm", NucleicScheme::Charmm),
+    ];
+    for (key, scheme) in nucleic {
+        let custom = &schemes[key];
+        for &pos in scheme.positions() {
+            for residue in scheme.residues(pos) {
+                let builtin: Vec<_> = scheme.residue_atoms(pos, residue).unwrap().collect();
+                let loaded: Vec<_> = custom
+                    .residue_atoms(pos, residue)
+                    .or_else(|| derived.get(key)?.residue_atoms(pos, residue))
+                    .or_else(|| extra.get(key)?.residue_atoms(pos, residue))
+                    .unwrap()
+                    .collect();
+                assert_eq!(builtin, loaded, "{key} {pos:?} {residue}");
+            }
+        }
+    }
+
+    let water = &schemes["tip3p"];
+    for atom in ["O", "H1", "H2"] {
+        assert_eq!(
+            water.charge(Position::Middle, "HOH", atom),
+            WaterScheme::Tip3p.charge(Position::Middle, "HOH", atom)
+        );
+    }
+
+    let ions = &schemes["classic"];
+    for residue in IonScheme::Classic.residues() {
+        let loaded: Vec<_> = ions
+            .residue_atoms(Position::Middle, residue)
+            .unwrap()
+            .collect();
+        assert_eq!(loaded.len(), 1, "{residue}");
+        assert_eq!(Some(loaded[0].1), IonScheme::Classic.charge(residue));
+    }
+}
+
+#[test]
+fn read_from_file() {
+    let file = std::fs::File::open("data/charges.csv").expect("Failed to open data/charges.csv");
+    let schemes = CustomScheme::read_csv(file).expect("Failed to read data/charges.csv");
+    assert_eq!(schemes, CustomScheme::parse_csv(CHARGES).unwrap());
+}
+
+// =============================================================================
+// Custom Tables
+// =============================================================================
+
+#[test]
+fn custom_scheme_as_provider() {
+    let text = "\
+# Ligand parameters
+scheme,position,residue,atom,charge
+gaff,,LIG,C1,0.1
+gaff,,LIG,O1,-0.1
+";
+    let schemes = CustomScheme::parse_csv(text).unwrap();
+    let gaff = schemes["gaff"].clone();
+
+    let chain = gaff.with_fallback(ProteinScheme::AmberFFSB);
+    assert_eq!(chain.charge(Position::NTerminal, "LIG", "O1"), Some(-0.1));
+    assert_eq!(
+        chain.charge(Position::Middle, "ALA", "CA"),
+        ProteinScheme::AmberFFSB.charge(Position::Middle, "ALA", "CA")
+    );
+
+    let registry = ChargeRegistry::new(&schemes["gaff"]).with_residue(
+        Position::Middle,
+        "LIG",
+        [("C1", 0.2), ("O1", -0.2)],
+    );
+    assert_eq!(registry.charge(Position::Middle, "LIG", "C1"), Some(0.2));
+    assert_eq!(registry.charge(Position::CTerminal, "LIG", "C1"), Some(0.1));
+}
+
+#[test]
+fn error_line_numbers() {
+    let text = "\
+scheme,position,residue,atom,charge
+# comment
+gaff,m,LIG,C1,0.1
+
+gaff,q,LIG,O1,-0.1
+";
+    let error = CustomScheme::parse_csv(text).unwrap_err();
+    assert_eq!(error.line, 5);
+    assert_eq!(error.kind, CsvErrorKind::UnknownPosition("q".into()));
+    assert_eq!(error.to_string(), "line 5: unknown position 'q'");
+}
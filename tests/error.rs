@@ -0,0 +1,102 @@
+#![cfg(all(
+    feature = "amber-ffsb",
+    feature = "amber-ff03",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "ions"
+))]
+
+use ffcharge::{ChargeError, IonScheme, NucleicScheme, Position, ProteinScheme};
+
+// =============================================================================
+// Protein Errors
+// =============================================================================
+
+#[test]
+fn protein_found() {
+    let charge = ProteinScheme::AmberFFSB.try_charge(Position::Middle, "ALA", "CA");
+    assert_eq!(
+        charge,
+        Ok(ProteinScheme::AmberFFSB
+            .charge(Position::Middle, "ALA", "CA")
+            .unwrap())
+    );
+}
+
+#[test]
+fn protein_unsupported_position() {
+    assert_eq!(
+        ProteinScheme::AmberFFSB.try_charge(Position::FivePrime, "ALA", "CA"),
+        Err(ChargeError::UnsupportedPosition(Position::FivePrime))
+    );
+}
+
+#[test]
+fn protein_unknown_residue() {
+    assert_eq!(
+        ProteinScheme::AmberFF03.try_charge(Position::Middle, "XYZ", "CA"),
+        Err(ChargeError::UnknownResidue("XYZ"))
+    );
+}
+
+#[test]
+fn protein_unknown_atom() {
+    assert_eq!(
+        ProteinScheme::Charmm.try_charge(Position::NTerminal, "GLY", "H"),
+        Err(ChargeError::UnknownAtom {
+            residue: "GLY",
+            atom: "H"
+        })
+    );
+}
+
+#[test]
+fn protein_try_residue() {
+    assert!(
+        ProteinScheme::AmberFFSB
+            .try_residue(Position::CTerminal, "LYS")
+            .is_ok()
+    );
+    assert_eq!(
+        ProteinScheme::AmberFFSB.try_residue(Position::ThreePrime, "LYS"),
+        Err(ChargeError::UnsupportedPosition(Position::ThreePrime))
+    );
+}
+
+// =============================================================================
+// Nucleic Acid Errors
+// =============================================================================
+
+#[test]
+fn nucleic_errors() {
+    let scheme = NucleicScheme::Amber;
+    assert!(scheme.try_charge(Position::Middle, "DA", "P").is_ok());
+    assert_eq!(
+        scheme.try_charge(Position::CTerminal, "DA", "P"),
+        Err(ChargeError::UnsupportedPosition(Position::CTerminal))
+    );
+    assert_eq!(
+        scheme.try_charge(Position::Middle, "DX", "P"),
+        Err(ChargeError::UnknownResidue("DX"))
+    );
+    assert_eq!(
+        scheme.try_charge(Position::FivePrime, "DA", "P"),
+        Err(ChargeError::UnknownAtom {
+            residue: "DA",
+            atom: "P"
+        })
+    );
+}
+
+// =============================================================================
+// Ion Errors
+// =============================================================================
+
+#[test]
+fn ion_errors() {
+    assert_eq!(IonScheme::Classic.try_charge("NA"), Ok(1.0));
+    assert_eq!(
+        IonScheme::Classic.try_charge("XX"),
+        Err(ChargeError::UnknownResidue("XX"))
+    );
+}
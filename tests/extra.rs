@@ -0,0 +1,78 @@
+use ffcharge::{ExtraScheme, Position};
+
+// =============================================================================
+// Enumeration
+// =============================================================================
+
+#[test]
+fn keys_round_trip() {
+    for &scheme in ExtraScheme::all() {
+        assert_eq!(ExtraScheme::from_key(scheme.key()), Some(scheme));
+        for &pos in scheme.positions() {
+            for residue in scheme.residues(pos) {
+                assert!(
+                    scheme.resolve(pos, residue).is_some(),
+                    "{scheme:?} {residue}"
+                );
+            }
+        }
+    }
+    assert_eq!(ExtraScheme::from_key("charmm"), None);
+}
+
+// =============================================================================
+// Fixture (FFCHARGE_EXTRA_DATA=tests/data/extra.csv)
+// =============================================================================
+
+#[test]
+#[cfg_attr(
+    not(ffcharge_extra_data),
+    ignore = "requires FFCHARGE_EXTRA_DATA=tests/data/extra.csv"
+)]
+fn fixture() {
+    let scheme = ExtraScheme::from_key("test-ligands")
+        .expect("FFCHARGE_EXTRA_DATA does not list tests/data/extra.csv");
+    assert_eq!(format!("{scheme:?}"), "ExtraScheme(\"test-ligands\")");
+
+    assert_eq!(scheme.charge(Position::NTerminal, "LIG", "C1"), Some(0.5));
+    assert_eq!(scheme.charge(Position::Middle, "LIG", "C1"), Some(0.25));
+    assert_eq!(scheme.charge(Position::FivePrime, "NA", "NA"), Some(1.0));
+    assert_eq!(scheme.positions().len(), 10);
+    assert_eq!(scheme.residues(Position::CTerminal), ["LIG", "NA"]);
+
+    // Position-independent templates share one entry.
+    assert_eq!(
+        scheme.resolve(Position::Middle, "LIG"),
+        scheme.resolve(Position::ThreePrime, "LIG")
+    );
+    assert_ne!(
+        scheme.resolve(Position::Middle, "LIG"),
+        scheme.resolve(Position::NTerminal, "LIG")
+    );
+
+    #[cfg(feature = "amber-ffsb")]
+    {
+        use ffcharge::ResidueChargeProvider;
+
+        let chain = scheme.with_fallback(ffcharge::ProteinScheme::AmberFFSB);
+        assert_eq!(chain.charge(Position::Middle, "LIG", "O1"), Some(-0.25));
+        assert_eq!(chain.charge(Position::Middle, "ALA", "CA"), Some(0.0337));
+    }
+}
+
+#[test]
+#[cfg(feature = "amber-ffsb")]
+#[cfg_attr(
+    not(ffcharge_extra_data),
+    ignore = "requires FFCHARGE_EXTRA_DATA=tests/data/extra.csv"
+)]
+fn fixture_extends_builtin_scheme() {
+    use ffcharge::ProteinScheme;
+
+    let scheme = ProteinScheme::AmberFFSB;
+    assert!(scheme.residues(Position::Middle).contains(&"MOD"));
+    assert_eq!(scheme.charge(Position::Middle, "MOD", "C1"), Some(0.3));
+    assert_eq!(scheme.charge(Position::Middle, "MOD", "O1"), Some(-0.3));
+    assert_eq!(scheme.residue(Position::Middle, "MOD").unwrap().len(), 2);
+    assert_eq!(scheme.charge(Position::NTerminal, "MOD", "C1"), None);
+}
@@ -0,0 +1,91 @@
+#![cfg(all(
+    feature = "amber-ffsb",
+    feature = "amber-ff03",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "water-tip3p",
+    feature = "water-spc-e",
+    feature = "water-opc3",
+    feature = "ions"
+))]
+
+use ffcharge::{ForceField, IonScheme, NucleicScheme, Position, ProteinScheme, WaterScheme};
+
+// =============================================================================
+// Presets
+// =============================================================================
+
+#[test]
+fn presets() {
+    let amber = ForceField::amber_ff14sb_tip3p();
+    assert_eq!(amber.protein, ProteinScheme::AmberFFSB);
+    assert_eq!(amber.nucleic, NucleicScheme::Amber);
+    assert_eq!(amber.water, WaterScheme::Tip3p);
+
+    assert_eq!(
+        ForceField::amber_ff03_tip3p().protein,
+        ProteinScheme::AmberFF03
+    );
+
+    let charmm = ForceField::charmm36m();
+    assert_eq!(charmm.protein, ProteinScheme::Charmm);
+    assert_eq!(charmm.nucleic, NucleicScheme::Charmm);
+    assert_eq!(charmm.ion, IonScheme::Classic);
+}
+
+// =============================================================================
+// Routing
+// =============================================================================
+
+#[test]
+fn mixed_system() {
+    let ff = ForceField::new(
+        ProteinScheme::AmberFF03,
+        NucleicScheme::Charmm,
+        WaterScheme::Opc3,
+        IonScheme::Classic,
+    );
+    let water = WaterScheme::Opc3.charges().expect("Missing: OPC3");
+    let cases = [
+        (
+            Position::CTerminal,
+            "LYS",
+            "NZ",
+            ProteinScheme::AmberFF03.charge(Position::CTerminal, "LYS", "NZ"),
+        ),
+        (
+            Position::ThreePrime,
+            "U",
+            "HO3'",
+            NucleicScheme::Charmm.charge(Position::ThreePrime, "U", "HO3'"),
+        ),
+        (Position::Middle, "HOH", "O", Some(water.o)),
+        (Position::Middle, "HOH", "H1", Some(water.h1)),
+        (Position::Middle, "MG", "MG", Some(2.0)),
+    ];
+    for (pos, residue, atom, expected) in cases {
+        assert!(expected.is_some());
+        assert_eq!(
+            ff.charge(pos, residue, atom),
+            expected,
+            "{}/{}",
+            residue,
+            atom
+        );
+    }
+}
+
+#[test]
+fn wrong_class_position() {
+    let ff = ForceField::default();
+    assert_eq!(ff.charge(Position::FivePrime, "ALA", "CA"), None);
+    assert_eq!(ff.charge(Position::NTerminal, "DA", "P"), None);
+}
+
+#[test]
+fn water_atoms() {
+    let w = WaterScheme::SpcE.charges().expect("Missing: SPC/E");
+    assert_eq!(w.charge("O"), Some(w.o));
+    assert_eq!(w.charge("H2"), Some(w.h2));
+    assert_eq!(w.charge("HW1"), None);
+}
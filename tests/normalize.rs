@@ -0,0 +1,69 @@
+#![cfg(all(
+    feature = "amber-ffsb",
+    feature = "charmm",
+    feature = "water-tip3p",
+    feature = "ions"
+))]
+
+use ffcharge::{IonScheme, NucleicScheme, Position, ProteinScheme, WaterScheme};
+
+// =============================================================================
+// Protein and Nucleic Acid
+// =============================================================================
+
+#[test]
+fn protein_mixed_case() {
+    let scheme = ProteinScheme::AmberFFSB.normalized();
+    let expected = ProteinScheme::AmberFFSB.charge(Position::Middle, "HID", "HD1");
+    assert!(expected.is_some());
+    for (residue, atom) in [
+        ("hid", "hd1"),
+        ("Hid", "Hd1"),
+        (" HID ", " HD1"),
+        ("HID", "HD1"),
+    ] {
+        assert_eq!(scheme.charge(Position::Middle, residue, atom), expected);
+    }
+    assert_eq!(
+        scheme.residue(Position::NTerminal, "ala"),
+        ProteinScheme::AmberFFSB.residue(Position::NTerminal, "ALA")
+    );
+}
+
+#[test]
+fn nucleic_mixed_case() {
+    let scheme = NucleicScheme::Charmm.normalized();
+    assert_eq!(
+        scheme.charge(Position::Middle, "da", "h2''"),
+        NucleicScheme::Charmm.charge(Position::Middle, "DA", "H2''")
+    );
+    assert!(scheme.resolve(Position::FivePrime, " u").is_some());
+}
+
+#[test]
+fn unknown_names() {
+    let scheme = ProteinScheme::Charmm.normalized();
+    assert_eq!(scheme.charge(Position::Middle, "xyz", "ca"), None);
+    assert_eq!(scheme.charge(Position::Middle, "alanine", "ca"), None);
+    assert_eq!(scheme.charge(Position::Middle, "a la", "ca"), None);
+}
+
+// =============================================================================
+// Water and Ion
+// =============================================================================
+
+#[test]
+fn water_mixed_case() {
+    let scheme = WaterScheme::Tip3p.normalized();
+    assert_eq!(scheme.charges("hoh"), WaterScheme::Tip3p.charges());
+    assert_eq!(scheme.charges(" Hoh "), WaterScheme::Tip3p.charges());
+    assert_eq!(scheme.charges("ALA"), None);
+}
+
+#[test]
+fn ion_mixed_case() {
+    let scheme = IonScheme::Classic.normalized();
+    assert_eq!(scheme.charge("Na"), Some(1.0));
+    assert_eq!(scheme.charge("cl "), Some(-1.0));
+    assert_eq!(scheme.charge("Zn"), IonScheme::Classic.charge("ZN"));
+}
@@ -0,0 +1,138 @@
+#![cfg(all(
+    feature = "amber-ffsb",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "water-tip3p",
+    feature = "water-opc3",
+    feature = "ions"
+))]
+
+use ffcharge::{
+    ForceField, IonScheme, NucleicScheme, Position, ProteinScheme, ResidueAtoms,
+    ResidueChargeProvider, WaterScheme,
+};
+
+/// Sums the charges of a residue through any provider.
+fn net_charge(provider: &dyn ResidueChargeProvider, pos: Position, residue: &str) -> Option<f32> {
+    provider
+        .residue_atoms(pos, residue)
+        .map(|atoms| atoms.map(|(_, c)| c).sum())
+}
+
+// =============================================================================
+// Built-in Schemes
+// =============================================================================
+
+#[test]
+fn dyn_dispatch() {
+    let providers: [&dyn ResidueChargeProvider; 5] = [
+        &ProteinScheme::Charmm,
+        &NucleicScheme::Amber,
+        &WaterScheme::Tip3p,
+        &IonScheme::Classic,
+        &ForceField::charmm36m(),
+    ];
+    let cases = [
+        (0, Position::Middle, "LYS"),
+        (1, Position::Middle, "DA"),
+        (2, Position::Middle, "HOH"),
+        (3, Position::Middle, "CA"),
+        (4, Position::CTerminal, "GLU"),
+    ];
+    for (i, pos, residue) in cases {
+        let atoms = providers[i]
+            .residue_atoms(pos, residue)
+            .unwrap_or_else(|| panic!("Missing: {}", residue));
+        for (atom, charge) in atoms {
+            assert_eq!(providers[i].charge(pos, residue, atom), Some(charge));
+        }
+    }
+}
+
+#[test]
+fn matches_inherent_methods() {
+    for &scheme in ProteinScheme::all() {
+        for &pos in scheme.positions() {
+            for res in scheme.residues(pos) {
+                let t = scheme.residue(pos, res).unwrap();
+                let atoms = ResidueChargeProvider::residue_atoms(&scheme, pos, res).unwrap();
+                assert_eq!(atoms.len(), t.len());
+                for ((atom, charge), (expected_atom, expected_charge)) in atoms.zip(t) {
+                    assert_eq!(atom, expected_atom);
+                    assert_eq!(charge, expected_charge);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn net_charges() {
+    let lys = net_charge(&ProteinScheme::AmberFFSB, Position::Middle, "LYS").unwrap();
+    assert!((lys - 1.0).abs() < 1e-4);
+    let water = net_charge(&WaterScheme::Opc3, Position::Middle, "HOH").unwrap();
+    assert!(water.abs() < 1e-4);
+    assert_eq!(
+        net_charge(&IonScheme::Classic, Position::Middle, "CL"),
+        Some(-1.0)
+    );
+    assert_eq!(
+        net_charge(&NucleicScheme::Amber, Position::Middle, "ALA"),
+        None
+    );
+}
+
+// =============================================================================
+// Fallback Chains
+// =============================================================================
+
+/// Reparameterized alanine with uniform charges.
+struct CustomAla;
+
+const ALA: &[(&str, f32)] = &[("N", -0.1), ("CA", 0.1)];
+
+impl ResidueChargeProvider for CustomAla {
+    fn charge(&self, pos: Position, residue: &str, atom: &str) -> Option<f32> {
+        self.residue_atoms(pos, residue)?
+            .find(|(a, _)| *a == atom)
+            .map(|(_, c)| c)
+    }
+
+    fn residue_atoms(&self, pos: Position, residue: &str) -> Option<ResidueAtoms<'_>> {
+        match (pos, residue) {
+            (Position::Middle, "ALA") => Some(ALA.into()),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn chain_order() {
+    let chain = CustomAla
+        .with_fallback(ProteinScheme::AmberFFSB)
+        .with_fallback(WaterScheme::Tip3p);
+    assert_eq!(chain.charge(Position::Middle, "ALA", "CA"), Some(0.1));
+    assert_eq!(chain.charge(Position::Middle, "ALA", "HA"), None);
+    assert_eq!(
+        chain.charge(Position::NTerminal, "ALA", "HA"),
+        ProteinScheme::AmberFFSB.charge(Position::NTerminal, "ALA", "HA")
+    );
+    assert_eq!(
+        chain.charge(Position::Middle, "HOH", "O"),
+        WaterScheme::Tip3p.charges().map(|w| w.o)
+    );
+    assert_eq!(
+        chain
+            .residue_atoms(Position::Middle, "ALA")
+            .map(|a| a.len()),
+        Some(2)
+    );
+}
+
+#[test]
+fn chain_by_reference() {
+    let custom = CustomAla;
+    let chain = (&custom).with_fallback(&ProteinScheme::Charmm);
+    assert_eq!(chain.charge(Position::Middle, "ALA", "N"), Some(-0.1));
+    assert!(chain.contains_residue(Position::Middle, "GLY"));
+}
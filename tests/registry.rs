@@ -0,0 +1,79 @@
+#![cfg(all(
+    feature = "alloc",
+    feature = "amber-ffsb",
+    feature = "charmm",
+    feature = "water-tip3p"
+))]
+
+use ffcharge::{
+    ChargeRegistry, NucleicScheme, Position, ProteinScheme, ResidueChargeProvider, WaterScheme,
+};
+
+// =============================================================================
+// Overlay
+// =============================================================================
+
+#[test]
+fn ligand_on_protein_scheme() {
+    let registry = ChargeRegistry::new(ProteinScheme::AmberFFSB).with_residue(
+        Position::Middle,
+        "LIG",
+        [("C1", 0.1), ("C2", -0.2), ("O1", 0.1)],
+    );
+    assert_eq!(registry.charge(Position::Middle, "LIG", "C2"), Some(-0.2));
+    assert_eq!(registry.charge(Position::NTerminal, "LIG", "C2"), None);
+    assert_eq!(
+        registry.charge(Position::Middle, "TRP", "NE1"),
+        ProteinScheme::AmberFFSB.charge(Position::Middle, "TRP", "NE1")
+    );
+    let names: Vec<_> = registry
+        .residue_atoms(Position::Middle, "LIG")
+        .expect("Missing: LIG")
+        .map(|(a, _)| a)
+        .collect();
+    assert_eq!(names, ["C1", "C2", "O1"]);
+}
+
+#[test]
+fn reparameterized_nucleotide() {
+    let mut registry = ChargeRegistry::new(NucleicScheme::Charmm);
+    let atoms: Vec<(String, f32)> = NucleicScheme::Charmm
+        .residue(Position::Middle, "DG")
+        .expect("Missing: Charmm/m/DG")
+        .iter()
+        .map(|(a, c)| (a.to_string(), if a == "N7" { c - 0.1 } else { c }))
+        .collect();
+    registry.insert(Position::Middle, "DG", atoms);
+
+    let base = NucleicScheme::Charmm
+        .charge(Position::Middle, "DG", "N7")
+        .unwrap();
+    let custom = registry.charge(Position::Middle, "DG", "N7").unwrap();
+    assert!((base - custom - 0.1).abs() < 1e-6);
+    assert_eq!(
+        registry.charge(Position::FivePrime, "DG", "N7"),
+        NucleicScheme::Charmm.charge(Position::FivePrime, "DG", "N7")
+    );
+
+    assert!(registry.remove(Position::Middle, "DG"));
+    assert_eq!(registry.charge(Position::Middle, "DG", "N7"), Some(base));
+}
+
+// =============================================================================
+// Provider Interface
+// =============================================================================
+
+#[test]
+fn as_provider() {
+    let registry = ChargeRegistry::new(ProteinScheme::Charmm).with_residue(
+        Position::Middle,
+        "NME",
+        [("N", -0.47), ("CH3", 0.47)],
+    );
+    let chain = registry.with_fallback(WaterScheme::Tip3p);
+    let provider: &dyn ResidueChargeProvider = &chain;
+    assert_eq!(provider.charge(Position::Middle, "NME", "CH3"), Some(0.47));
+    assert!(provider.contains_residue(Position::Middle, "HOH"));
+    assert!(provider.contains_residue(Position::Middle, "ALA"));
+    assert!(!provider.contains_residue(Position::Middle, "ACE"));
+}
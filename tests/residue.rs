@@ -0,0 +1,355 @@
+#![cfg(all(
+    feature = "amber-ffsb",
+    feature = "amber-nucleic",
+    feature = "charmm",
+    feature = "ions"
+))]
+
+use ffcharge::{
+    IonScheme, NucleicPosition, NucleicScheme, Position, ProteinPosition, ProteinScheme,
+};
+
+// =============================================================================
+// Protein Templates
+// =============================================================================
+
+#[test]
+fn protein_template_order() {
+    let ala = ProteinScheme::AmberFFSB
+        .residue(Position::Middle, "ALA")
+        .expect("Missing: AmberFFSB/m/ALA");
+    assert!(
+        ala.atom_names()
+            .eq(["N", "H", "CA", "HA", "CB", "HB1", "HB2", "HB3", "C", "O"])
+    );
+    assert_eq!(ala.atoms().len(), ala.len());
+    #[cfg(not(feature = "compact"))]
+    assert!(ala.atoms().iter().copied().eq(ala.iter()));
+    #[cfg(feature = "compact")]
+    assert!(ala.atoms().eq(ala.iter()));
+}
+
+#[test]
+fn protein_template_matches_charge() {
+    for &scheme in ProteinScheme::all() {
+        for pos in [
+            Position::NTerminal,
+            Position::NTerminalDeprotonated,
+            Position::Middle,
+            Position::CTerminal,
+            Position::CTerminalProtonated,
+        ] {
+            let t = scheme.residue(pos, "TRP").expect("Missing: TRP");
+            for (atom, charge) in t {
+                assert_eq!(scheme.charge(pos, "TRP", atom), Some(charge));
+            }
+        }
+    }
+}
+
+#[test]
+fn protein_template_terminal_atoms() {
+    let n = ProteinScheme::Charmm
+        .residue(Position::NTerminal, "GLY")
+        .expect("Missing: Charmm/n/GLY");
+    let c = ProteinScheme::Charmm
+        .residue(Position::CTerminalProtonated, "GLY")
+        .expect("Missing: Charmm/c+/GLY");
+    assert!(n.contains("H3") && !n.contains("H"));
+    assert!(c.contains("OXT") && c.contains("HOXT"));
+}
+
+#[test]
+fn protein_template_missing() {
+    assert!(
+        ProteinScheme::AmberFFSB
+            .residue(Position::Middle, "XYZ")
+            .is_none()
+    );
+    assert!(
+        ProteinScheme::AmberFFSB
+            .residue(Position::FivePrime, "ALA")
+            .is_none()
+    );
+}
+
+#[test]
+#[cfg(feature = "derived-templates")]
+fn protein_template_free_amino_acid() {
+    for &scheme in ProteinScheme::all() {
+        let zwitterion = scheme
+            .residue(Position::NCTerminal, "ALA")
+            .expect("Missing: nc/ALA");
+        let neutral = scheme
+            .residue(Position::NCTerminalNeutral, "ALA")
+            .expect("Missing: n-c+/ALA");
+        for atom in ["H1", "H2", "H3", "OXT"] {
+            assert!(zwitterion.contains(atom), "{:?}/nc/ALA/{}", scheme, atom);
+        }
+        for atom in ["H1", "H2", "OXT", "HOXT"] {
+            assert!(neutral.contains(atom), "{:?}/n-c+/ALA/{}", scheme, atom);
+        }
+        assert!(!zwitterion.contains("H") && !neutral.contains("H"));
+        assert!(!neutral.contains("H3"));
+    }
+}
+
+// =============================================================================
+// Nucleic Acid Templates
+// =============================================================================
+
+#[test]
+fn nucleic_template_matches_charge() {
+    for &scheme in NucleicScheme::all() {
+        for pos in [Position::FivePrime, Position::Middle, Position::ThreePrime] {
+            let t = scheme.residue(pos, "DG").expect("Missing: DG");
+            assert!(!t.is_empty());
+            for (atom, charge) in t {
+                assert_eq!(scheme.charge(pos, "DG", atom), Some(charge));
+            }
+        }
+    }
+}
+
+#[test]
+fn nucleic_template_five_prime_has_no_phosphate() {
+    let t = NucleicScheme::Amber
+        .residue(Position::FivePrime, "DA")
+        .expect("Missing: Amber/5/DA");
+    assert!(!t.contains("P"));
+    assert!(
+        NucleicScheme::Amber
+            .residue(Position::Middle, "DA")
+            .expect("Missing: Amber/m/DA")
+            .contains("P")
+    );
+}
+
+#[test]
+#[cfg(feature = "derived-templates")]
+fn nucleic_template_nucleoside() {
+    for &scheme in NucleicScheme::all() {
+        let t = scheme
+            .residue(Position::FiveThreePrime, "C")
+            .expect("Missing: 53/C");
+        assert!(t.contains("HO5'") && t.contains("HO3'"));
+        assert!(!t.contains("P") && !t.contains("OP1"));
+    }
+}
+
+#[test]
+fn nucleic_template_missing() {
+    assert!(
+        NucleicScheme::Amber
+            .residue(Position::NTerminal, "DA")
+            .is_none()
+    );
+}
+
+// =============================================================================
+// Enumeration
+// =============================================================================
+
+#[test]
+fn protein_positions() {
+    for &scheme in ProteinScheme::all() {
+        assert_eq!(
+            scheme.positions(),
+            &[
+                Position::NTerminal,
+                Position::NTerminalDeprotonated,
+                Position::CTerminal,
+                Position::CTerminalProtonated,
+                #[cfg(feature = "derived-templates")]
+                Position::NCTerminal,
+                #[cfg(feature = "derived-templates")]
+                Position::NCTerminalNeutral,
+                Position::Middle,
+            ]
+        );
+    }
+}
+
+#[test]
+fn nucleic_positions() {
+    for &scheme in NucleicScheme::all() {
+        assert_eq!(
+            scheme.positions(),
+            &[
+                Position::FivePrime,
+                Position::ThreePrime,
+                #[cfg(feature = "derived-templates")]
+                Position::FiveThreePrime,
+                Position::Middle,
+            ]
+        );
+    }
+}
+
+#[test]
+fn protein_residues_resolve() {
+    for &scheme in ProteinScheme::all() {
+        for &pos in scheme.positions() {
+            let residues = scheme.residues(pos);
+            assert!(residues.is_sorted());
+            for res in residues {
+                assert!(
+                    scheme.residue(pos, res).is_some(),
+                    "{:?}/{:?}/{}",
+                    scheme,
+                    pos,
+                    res
+                );
+            }
+        }
+    }
+    for &scheme in ProteinScheme::all() {
+        // The extra data fixture (tests/data/extra.csv) adds MOD to amber-ffsb.
+        let extra = cfg!(ffcharge_extra_data) && scheme == ProteinScheme::AmberFFSB;
+        assert_eq!(
+            scheme.residues(Position::Middle).len(),
+            29 + usize::from(extra)
+        );
+    }
+    assert!(
+        ProteinScheme::AmberFFSB
+            .residues(Position::FivePrime)
+            .is_empty()
+    );
+}
+
+#[test]
+fn nucleic_residues_resolve() {
+    for &scheme in NucleicScheme::all() {
+        for &pos in scheme.positions() {
+            let residues = scheme.residues(pos);
+            assert!(residues.is_sorted());
+            for res in residues {
+                assert!(
+                    scheme.residue(pos, res).is_some(),
+                    "{:?}/{:?}/{}",
+                    scheme,
+                    pos,
+                    res
+                );
+            }
+        }
+        assert_eq!(scheme.residues(Position::FivePrime).len(), 10);
+    }
+    assert!(
+        NucleicScheme::Amber
+            .residues(Position::CTerminal)
+            .is_empty()
+    );
+}
+
+#[test]
+fn ion_residues_resolve() {
+    let residues = IonScheme::Classic.residues();
+    assert_eq!(residues.len(), 66);
+    assert!(residues.is_sorted());
+    for res in residues {
+        assert!(IonScheme::Classic.charge(res).is_some(), "{}", res);
+    }
+}
+
+// =============================================================================
+// Molecule Class Isolation
+// =============================================================================
+
+#[test]
+fn charmm_protein_excludes_nucleotides() {
+    let scheme = ProteinScheme::Charmm;
+    assert_eq!(scheme.charge(Position::Middle, "DA", "P"), None);
+    assert!(scheme.residue(Position::Middle, "A").is_none());
+    assert!(!scheme.residues(Position::Middle).contains(&"DA"));
+}
+
+#[test]
+fn charmm_nucleic_excludes_amino_acids() {
+    let scheme = NucleicScheme::Charmm;
+    assert_eq!(scheme.charge(Position::Middle, "ALA", "CA"), None);
+    assert!(scheme.residue(Position::Middle, "GLY").is_none());
+    assert_eq!(scheme.residues(Position::Middle).len(), 10);
+}
+
+// =============================================================================
+// Typed Positions
+// =============================================================================
+
+#[test]
+fn typed_protein_positions_match_general() {
+    for &scheme in ProteinScheme::all() {
+        for &pos in ProteinPosition::all() {
+            assert_eq!(
+                scheme.residue(pos, "SER"),
+                scheme.residue(Position::from(pos), "SER")
+            );
+            assert_eq!(
+                scheme.try_charge(pos, "SER", "OG"),
+                scheme.try_charge(Position::from(pos), "SER", "OG")
+            );
+        }
+    }
+}
+
+#[test]
+fn typed_nucleic_positions_match_general() {
+    for &scheme in NucleicScheme::all() {
+        for &pos in NucleicPosition::all() {
+            assert_eq!(
+                scheme.residue(pos, "U"),
+                scheme.residue(Position::from(pos), "U")
+            );
+            assert_eq!(scheme.residues(pos), scheme.residues(Position::from(pos)));
+        }
+    }
+}
+
+// =============================================================================
+// Handles
+// =============================================================================
+
+#[test]
+fn handle_matches_named_lookup() {
+    for &scheme in ProteinScheme::all() {
+        for &pos in scheme.positions() {
+            for res in scheme.residues(pos) {
+                let h = scheme.resolve(pos, res).expect("Missing handle");
+                assert_eq!(Some(h.template()), scheme.residue(pos, res));
+                for (atom, charge) in h.template() {
+                    let id = h.atom_index(atom).expect("Missing atom");
+                    assert_eq!(h.charge_by_id(id), Some(charge));
+                    assert_eq!(h.charge(atom), scheme.charge(pos, res, atom));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn nucleic_handle() {
+    let h = NucleicScheme::Amber
+        .resolve(NucleicPosition::FivePrime, "DA")
+        .expect("Missing: Amber/5/DA");
+    assert_eq!(h.atom_index("P"), None);
+    let id = h.atom_index("HO5'").expect("Missing: HO5'");
+    assert_eq!(
+        h.charge_by_id(id),
+        NucleicScheme::Amber.charge(Position::FivePrime, "DA", "HO5'")
+    );
+}
+
+#[test]
+fn handle_missing() {
+    assert!(
+        ProteinScheme::AmberFFSB
+            .resolve(Position::Middle, "XYZ")
+            .is_none()
+    );
+    assert!(
+        NucleicScheme::Charmm
+            .resolve(Position::NTerminal, "DA")
+            .is_none()
+    );
+}